@@ -1,25 +1,124 @@
 #![no_std]
+// `#[contractimpl]` generates client and args types mirroring long entry points such as
+// `create_package`, where an item-level allow does not reach.
+#![allow(clippy::too_many_arguments)]
 
 use soroban_sdk::{
-    Address, Env, Map, String, Symbol, contract, contracterror, contractevent, contractimpl,
-    contracttype, symbol_short, token,
+    Address, BytesN, Env, IntoVal, Map, String, Symbol, Val, Vec, contract, contractclient,
+    contracterror, contractevent, contractimpl, contracttype, symbol_short, token,
 };
 
 // --- Storage Keys ---
 const KEY_ADMIN: Symbol = symbol_short!("admin");
+const KEY_PENDING_ADMIN: Symbol = symbol_short!("pend_adm");
+const KEY_ADMIN_PROPOSED_AT: Symbol = symbol_short!("adm_prop"); // u64 timestamp
+const KEY_ADMIN_DELAY: Symbol = symbol_short!("adm_delay"); // u64 seconds
 const KEY_TOTAL_LOCKED: Symbol = symbol_short!("locked"); // Map<Address, i128>
+const KEY_OPERATORS: Symbol = symbol_short!("ops"); // Map<Address, bool> (persistent)
+const KEY_TOKEN_WHITELIST: Symbol = symbol_short!("tok_wl"); // Map<Address, bool>
+const KEY_TOKEN_WL_ENABLED: Symbol = symbol_short!("tok_wl_on"); // bool
+const KEY_PAUSED: Symbol = symbol_short!("paused"); // bool
+const KEY_FEE_RATES: Symbol = symbol_short!("fee_rates"); // Map<Address, u32>
+const KEY_FEES_ACCRUED: Symbol = symbol_short!("fees"); // Map<Address, i128>
+const KEY_DEFAULT_EXPIRY: Symbol = symbol_short!("def_exp"); // u64 seconds
+const KEY_RATE_LIMIT: Symbol = symbol_short!("rate_lim"); // (u32, u32): window ledgers, max creates
+const KEY_RATE_STATE: Symbol = symbol_short!("rate_st"); // (u32, u32): window start, creates in window
+const KEY_MIN_EXPIRY: Symbol = symbol_short!("min_exp"); // u64 seconds, 0 = no minimum
+const KEY_MAX_EXPIRY: Symbol = symbol_short!("max_exp"); // u64 seconds, 0 = no maximum
+const KEY_GRACE_PERIOD: Symbol = symbol_short!("grace"); // u64 seconds
+const KEY_GLOBAL_EXPIRY: Symbol = symbol_short!("glob_exp"); // u64 timestamp, 0 = no override
+const KEY_AUTO_REFUND: Symbol = symbol_short!("auto_rfd"); // bool
+const KEY_AUTO_INCREMENT: Symbol = symbol_short!("auto_inc"); // bool: contract assigns create_package IDs
+const KEY_REFUND_DEST: Symbol = symbol_short!("rfd_dest"); // Address
+const KEY_MIN_AMOUNTS: Symbol = symbol_short!("min_amts"); // Map<Address, i128>
+const KEY_MAX_AMOUNTS: Symbol = symbol_short!("max_amts"); // Map<Address, i128>
+const KEY_DEFAULT_TTL: Symbol = symbol_short!("def_ttl"); // u32 ledgers
+const KEY_INSTANCE_TTL: Symbol = symbol_short!("inst_ttl"); // u32 live-until ledger of the instance
+const KEY_ALLOWLIST: Symbol = symbol_short!("allow"); // (persistent) (KEY_ALLOWLIST, Address) -> bool
+const KEY_ALLOWLIST_MODE: Symbol = symbol_short!("allow_on"); // bool
+const KEY_BLACKLIST: Symbol = symbol_short!("blacklist"); // (persistent) (KEY_BLACKLIST, Address) -> bool
+const KEY_RECIPIENT_LOCKED: Symbol = symbol_short!("rcpt_lck"); // (persistent) (KEY_RECIPIENT_LOCKED, recipient, token) -> i128
+const KEY_MAX_RECIPIENT_PKGS: Symbol = symbol_short!("max_rpkgs"); // u32, 0 = unlimited
+const KEY_RECIPIENT_PKGS: Symbol = symbol_short!("rcpt_ids"); // (persistent) paged ID list per (KEY_RECIPIENT_PKGS, recipient)
+const KEY_RECIPIENT_PKG_COUNT: Symbol = symbol_short!("rcpt_pkgs"); // (persistent) (KEY_RECIPIENT_PKG_COUNT, Address) -> u32
+const KEY_KYC_ATTESTOR: Symbol = symbol_short!("kyc_att"); // Address
+const KEY_KYC: Symbol = symbol_short!("kyc_rcpt"); // (persistent) (KEY_KYC, recipient) -> bool: attested recipients
+const KEY_RECIPIENT_INDEX: Symbol = symbol_short!("rcpt_idx"); // (persistent) (KEY_RECIPIENT_INDEX, recipient) -> Vec<u64>: active ids
+const KEY_TOKEN_PKG_INDEX: Symbol = symbol_short!("tok_pkgs"); // (persistent) paged ID list per (KEY_TOKEN_PKG_INDEX, token)
+const KEY_NONCES: Symbol = symbol_short!("nonces"); // (persistent) (KEY_NONCES, BytesN<32>) -> package id
+const KEY_EXT_REF_INDEX: Symbol = symbol_short!("ext_ref"); // (persistent) (KEY_EXT_REF_INDEX, String) -> u64
+const KEY_METADATA_LOCKED: Symbol = symbol_short!("meta_lck"); // (persistent) (KEY_METADATA_LOCKED, id) -> bool
+const KEY_PRIORITY_LEVELS: Symbol = symbol_short!("prio_lvls"); // Vec<u32> (persistent): non-empty levels, ascending
+const KEY_CATEGORY_INDEX: Symbol = symbol_short!("cat_idx"); // (persistent) (KEY_CATEGORY_INDEX, category) -> active ids
+const KEY_CATEGORIES: Symbol = symbol_short!("cats"); // Vec<Symbol> (persistent): categories with active ids
+const KEY_WASM_HASH: Symbol = symbol_short!("wasm"); // BytesN<32> installed by the last upgrade
+const KEY_SIGNERS: Symbol = symbol_short!("signers"); // Vec<Address>
+const KEY_THRESHOLD: Symbol = symbol_short!("threshold"); // u32
+const KEY_TOTAL_FUNDED: Symbol = symbol_short!("tot_fund"); // Map<Address, i128>
+const KEY_TOTAL_DISBURSED: Symbol = symbol_short!("tot_disb"); // Map<Address, i128>
+const KEY_TOTAL_REFUNDED: Symbol = symbol_short!("tot_rfd"); // Map<Address, i128>
+const KEY_TOTAL_CLAIMED: Symbol = symbol_short!("tot_clm"); // Map<Address, i128>: net paid to recipients
+const KEY_TOTAL_CANCELLED: Symbol = symbol_short!("tot_cncl"); // Map<Address, i128>: awaiting refund
+const KEY_TOTAL_DONATIONS: Symbol = symbol_short!("tot_don"); // Map<Address, i128>
+const KEY_DONOR_LEDGER: Symbol = symbol_short!("donors"); // (persistent) (KEY_DONOR_LEDGER, donor, token) -> i128
+const KEY_FUND_ATTRIBUTION: Symbol = symbol_short!("fund_org"); // (persistent) (KEY_FUND_ATTRIBUTION, org) -> i128
+const KEY_ALL_PKG_IDS: Symbol = symbol_short!("all_ids"); // (persistent) paged ID list, creation order
+// Package counters (persistent, u64). Terminal counters track packages *currently* in that status.
+const KEY_PKG_COUNT: Symbol = symbol_short!("pkg_cnt");
+const KEY_PROP_COUNT: Symbol = symbol_short!("prop_cnt");
+const KEY_ADMIN_LOG: Symbol = symbol_short!("alog"); // (alog, n) -> AdminLogEntry
+const KEY_ADMIN_LOG_COUNT: Symbol = symbol_short!("adm_lcnt");
+const KEY_NEXT_ID: Symbol = symbol_short!("next_id"); // Cursor for contract-assigned IDs
+const KEY_CNT_CLAIMED: Symbol = symbol_short!("cnt_clm");
+const KEY_CNT_DISBURSED: Symbol = symbol_short!("cnt_disb");
+const KEY_CNT_CANCELLED: Symbol = symbol_short!("cnt_cncl");
+const KEY_CNT_EXPIRED: Symbol = symbol_short!("cnt_exp");
+const KEY_CNT_REFUNDED: Symbol = symbol_short!("cnt_rfd");
+const KEY_CNT_TRANSFERRED: Symbol = symbol_short!("cnt_xfer");
+
+const KEY_VERSION: Symbol = symbol_short!("version"); // String
+const CONTRACT_VERSION: &str = "1.0.0";
+
+const MAX_FEE_RATE_BPS: u32 = 1_000; // 10%
+const MAX_MEMO_LEN: u32 = 256;
+const MAX_PRIORITY: u32 = u8::MAX as u32; // package priorities are 0..=255
+const MAX_INSTANCE_TTL_EXTENSION: u32 = 3_110_400; // ~1 year at 5s per ledger
+// Index entries kept in persistent storage are extended to ~90 days whenever they are
+// written with less than ~30 days left.
+const INDEX_TTL_THRESHOLD: u32 = 518_400;
+const INDEX_TTL_EXTEND_TO: u32 = 1_555_200;
+const EXPIRY_BUCKET_SECONDS: u64 = 604_800; // One week per expiry index bucket
+const ID_PAGE_SIZE: u32 = 500; // IDs per page of an append-only ID list
+const BPS_DENOMINATOR: i128 = 10_000;
 
 // --- Data Types ---
 
+/// Migration note: `Disbursed` took ordinal 5, moving `PartiallyClaimed` from 5 to 6.
+/// Packages stored as `PartiallyClaimed` by an older build decode as `Disbursed`, and
+/// admin-disbursed packages from before the split remain `Claimed`; off-chain indexers
+/// must remap ordinals for data written before the upgrade.
 #[contracttype]
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
 #[repr(u32)]
 pub enum PackageStatus {
     Created = 0,
-    Claimed = 1,
+    Claimed = 1, // Paid out by the recipient's own claim
     Expired = 2,
     Cancelled = 3,
     Refunded = 4,
+    Disbursed = 5, // Paid out by the admin via `disburse`/`disburse_to`
+    PartiallyClaimed = 6,
+    Transferred = 7, // Moved to another escrow via `transfer_package_to_contract`
+}
+
+impl PackageStatus {
+    /// Active packages still hold locked funds and can be claimed, disbursed, or revoked.
+    pub fn is_active(&self) -> bool {
+        matches!(
+            self,
+            PackageStatus::Created | PackageStatus::PartiallyClaimed
+        )
+    }
 }
 
 #[contracttype]
@@ -28,13 +127,235 @@ pub struct Package {
     pub id: u64,
     pub recipient: Address,
     pub amount: i128,
+    pub remaining_amount: i128, // Still locked; decreases with each partial claim
     pub token: Address,
     pub status: PackageStatus,
     pub created_at: u64,
     pub expires_at: u64,
+    pub start_at: u64, // 0 = claimable immediately
+    pub metadata: Map<Symbol, String>,
+    pub external_ref: String, // Off-chain case/ticket ID; empty if unused
+    // 0 = normal ... MAX_PRIORITY = critical. Logically a u8; u32 because Soroban
+    // contract types have no u8.
+    pub priority: u32,
+}
+
+/// Reusable configuration for stamping out identical packages.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct PackageTemplate {
+    pub token: Address,
+    pub amount: i128,
+    pub expiry_duration: u64, // 0 = packages never expire
     pub metadata: Map<Symbol, String>,
 }
 
+/// Linear release schedule attached to a package under `("vest", id)`.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct VestingSchedule {
+    pub start_at: u64,
+    pub end_at: u64,
+    pub cliff_at: u64, // Nothing vests before this; 0 = no cliff
+    pub total_amount: i128,
+    pub claimed_amount: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ComparisonOp {
+    GreaterThan = 0,
+    LessThan = 1,
+    Equals = 2,
+}
+
+/// Whether the pool backing a single package currently holds enough of its token.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SolvencyStatus {
+    Solvent = 0,
+    Underfunded = 1,
+    NotApplicable = 2, // Package is terminal and holds no claim on the pool
+}
+
+/// Claims are only allowed while `oracle.get_value(data_key) <comparison> threshold` holds.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct OracleCondition {
+    pub oracle: Address,
+    pub data_key: Symbol,
+    pub threshold: i128,
+    pub comparison: ComparisonOp,
+}
+
+/// Interface a destination escrow exposes to accept packages from `transfer_package_to_contract`.
+/// Returns the ID the package was created under.
+#[contractclient(name = "EscrowReceiverClient")]
+pub trait IEscrowReceiver {
+    fn receive_transferred_package(
+        env: Env,
+        id: u64,
+        recipient: Address,
+        amount: i128,
+        token: Address,
+        expires_at: u64,
+    ) -> u64;
+}
+
+/// Interface the contract expects from an oracle referenced by an `OracleCondition`.
+#[contractclient(name = "OracleClient")]
+pub trait OracleInterface {
+    fn get_value(env: Env, key: Symbol) -> i128;
+}
+
+/// Re-creation settings for a recurring package, stored under `("rec", id)`.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct RecurringConfig {
+    pub interval_seconds: u64,
+    pub max_recurrences: u32,
+    pub recurrence_count: u32, // Recurrences already created before this package
+}
+
+/// One stage of a milestone package, stored in a `Vec` under `("ms", package_id)`.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Milestone {
+    pub id: u32,
+    pub amount: i128,
+    pub released: bool, // Admin has approved this stage
+    pub claimed: bool,
+    pub description: String,
+}
+
+/// Admin operations that require multisig approval once signers are configured.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum AdminAction {
+    Refund = 0,
+    Revoke = 1,
+    WithdrawExcess = 2,
+    TransferAdmin = 3,
+    Upgrade = 4,
+    SetMultisig = 5,
+    DisburseTo = 6,
+}
+
+/// Arguments for an `AdminAction`. Only the fields the action needs are read:
+/// `Refund`/`Revoke` use `package_id`, `WithdrawExcess` uses `token` and `amount`,
+/// `TransferAdmin` uses `address`, `Upgrade` uses `wasm_hash` and `version`,
+/// `SetMultisig` uses `signers` and `threshold`, `DisburseTo` uses `package_id` and `address`.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ActionParams {
+    pub package_id: u64,
+    pub token: Option<Address>,
+    pub amount: i128,
+    pub address: Option<Address>,
+    pub wasm_hash: Option<BytesN<32>>,
+    pub version: Option<String>,
+    pub signers: Vec<Address>,
+    pub threshold: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Proposal {
+    pub id: u64,
+    pub action: AdminAction,
+    pub params: ActionParams,
+    pub approvals: Vec<Address>,
+    pub executed: bool,
+}
+
+/// One append-only record of an admin (or operator) decision.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct AdminLogEntry {
+    pub action: Symbol,
+    pub package_id: Option<u64>,
+    pub timestamp: u64,
+    pub actor: Address,
+    pub details: String,
+}
+
+/// One entry of a package's status history, stored under `("hist", id)`.
+/// The first entry is always `Created -> Created` at creation time.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct StatusTransition {
+    pub from_status: PackageStatus,
+    pub to_status: PackageStatus,
+    pub timestamp: u64,
+    pub actor: Address,
+    pub memo: String, // Recipient note from `claim_with_memo`; empty otherwise
+}
+
+/// Outcome of a batch operation: IDs that succeeded and IDs that failed, with the
+/// failure's `Error` code (`Error::X as u32`; error enums can't be nested in contract types).
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct BatchResult {
+    pub successes: Vec<u64>,
+    pub failures: Vec<(u64, u32)>,
+}
+
+/// Dry run of `claim`: the fee breakdown for the package's remaining amount and, if the
+/// claim would fail right now, the code of the `Error` it would fail with.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ClaimSimulation {
+    pub gross_amount: i128,
+    pub fee_amount: i128,
+    pub net_amount: i128,
+    pub fee_rate_bps: u32,
+    pub is_claimable: bool,
+    pub failure_reason: Option<u32>,
+}
+
+/// Snapshot of the contract's instance-storage configuration.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ContractConfig {
+    pub admin: Address,
+    pub paused: bool,
+    pub token_whitelist_enabled: bool,
+    pub default_expiry_duration: Option<u64>,
+    pub allowlist_mode: bool,
+    pub fee_rates: Map<Address, u32>,
+}
+
+/// One-call snapshot of per-token totals and global package counters for dashboards.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ContractStats {
+    pub total_funded: i128,
+    pub total_disbursed: i128, // Gross amount released via claims and disbursements
+    pub total_refunded: i128,
+    pub total_locked: i128,
+    pub available_balance: i128,
+    pub packages_created: u64,
+    pub packages_claimed: u64,
+    pub packages_disbursed: u64,
+    pub packages_cancelled: u64,
+    pub packages_expired: u64,
+    pub packages_refunded: u64,
+    pub packages_transferred: u64,
+}
+
+/// Per-token money flow. Ignoring funds taken out by `withdraw_excess` and `collect_fees`,
+/// `total_funded == total_claimed + total_refunded + total_cancelled + total_locked + available`.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct TokenBreakdown {
+    pub total_locked: i128,    // Active package funds plus uncollected fees
+    pub total_claimed: i128,   // Net amount paid to recipients
+    pub total_refunded: i128,  // Sent to the refund destination
+    pub total_cancelled: i128, // Held by cancelled/expired packages awaiting refund
+    pub total_funded: i128,
+    pub available: i128, // Balance not locked and not owed to pending refunds
+}
+
 #[contracterror]
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
 pub enum Error {
@@ -49,36 +370,151 @@ pub enum Error {
     InsufficientFunds = 9, // Contract balance < Total Locked + New Amount
     PackageIdExists = 10,
     InvalidState = 11, // Transition not allowed
+    NoPendingAdmin = 12,
+    TokenNotAllowed = 13,
+    ContractPaused = 14,
+    InvalidFeeRate = 15,
+    InvalidExpiry = 16,
+    NoDefaultExpiry = 17,
+    AmountBelowMinimum = 18,
+    AmountAboveMaximum = 19,
+    PackageNotYetClaimable = 20,
+    TemplateNotFound = 21,
+    RecipientNotAllowed = 22,
+    RecipientBlacklisted = 23,
+    ExternalRefExists = 24,
+    InvalidSchedule = 25,
+    MilestoneNotFound = 26,
+    ConditionNotMet = 27,
+    MultisigRequired = 28, // Action must go through propose_action/approve_proposal
+    InvalidThreshold = 29,
+    ProposalNotFound = 30,
+    AlreadyApproved = 31,
+    RecipientPackageLimitExceeded = 32,
+    TokenMismatch = 33,
+    MetadataLocked = 34,
+    ExpiryTooShort = 35,
+    ExpiryTooLong = 36,
+    InvalidPriority = 37,
+    MemoTooLong = 38,
+    AutoRefundDisabled = 39,
+    TransferDelayNotElapsed = 40,
+    RateLimitExceeded = 41,
+    AutoIncrementEnabled = 42,
+    InvalidTtlExtension = 43,
+    KycRequired = 44, // Package requires a KYC attestation the recipient does not have
+    AlreadyRecurring = 45,
+}
+
+impl Error {
+    /// Human-readable explanation of the error, served by `error_description`.
+    pub fn description(&self) -> &'static str {
+        match self {
+            Error::NotInitialized => "Contract has not been initialized",
+            Error::AlreadyInitialized => "Contract is already initialized",
+            Error::NotAuthorized => "Caller is not authorized for this action",
+            Error::InvalidAmount => "Amount must be positive and within the package balance",
+            Error::PackageNotFound => "No package exists with this ID",
+            Error::PackageNotActive => "Package is no longer active",
+            Error::PackageExpired => "Package has passed its expiry timestamp",
+            Error::PackageNotExpired => "Package has not expired yet",
+            Error::InsufficientFunds => "Contract balance cannot cover this amount",
+            Error::PackageIdExists => "A package with this ID already exists",
+            Error::InvalidState => "Package status does not allow this transition",
+            Error::NoPendingAdmin => "No admin transfer is pending",
+            Error::TokenNotAllowed => "Token is not on the whitelist",
+            Error::ContractPaused => "Contract is paused",
+            Error::InvalidFeeRate => "Fee rate exceeds the maximum allowed",
+            Error::InvalidExpiry => "Expiry timestamp must be in the future",
+            Error::NoDefaultExpiry => "No default expiry duration is configured",
+            Error::AmountBelowMinimum => "Amount is below the minimum for this token",
+            Error::AmountAboveMaximum => "Amount is above the maximum for this token",
+            Error::PackageNotYetClaimable => "Package cannot be claimed before its start time",
+            Error::TemplateNotFound => "No template exists with this ID",
+            Error::RecipientNotAllowed => "Recipient is not on the allowlist",
+            Error::RecipientBlacklisted => "Recipient is blacklisted",
+            Error::ExternalRefExists => "External reference is already used by another package",
+            Error::InvalidSchedule => "Schedule parameters are invalid",
+            Error::MilestoneNotFound => "No milestone exists with this ID",
+            Error::ConditionNotMet => "Oracle condition for this package is not met",
+            Error::MultisigRequired => "Action must go through a multisig proposal",
+            Error::InvalidThreshold => "Multisig threshold is out of range",
+            Error::ProposalNotFound => "No proposal exists with this ID",
+            Error::AlreadyApproved => "Signer has already approved this proposal",
+            Error::RecipientPackageLimitExceeded => {
+                "Recipient has reached the active package limit"
+            }
+            Error::TokenMismatch => "Packages use different tokens",
+            Error::MetadataLocked => "Package metadata is locked",
+            Error::ExpiryTooShort => "Expiry is sooner than the minimum duration",
+            Error::ExpiryTooLong => "Expiry is later than the maximum duration",
+            Error::InvalidPriority => "Priority exceeds the maximum level",
+            Error::MemoTooLong => "Memo exceeds the maximum length",
+            Error::AutoRefundDisabled => "Automatic refunds are disabled",
+            Error::TransferDelayNotElapsed => "Admin transfer delay has not elapsed",
+            Error::RateLimitExceeded => "Package creation rate limit reached",
+            Error::AutoIncrementEnabled => "Package IDs are assigned automatically; pass 0",
+            Error::InvalidTtlExtension => "TTL extension is zero or above the maximum",
+            Error::KycRequired => "Recipient must be KYC-attested to claim this package",
+            Error::AlreadyRecurring => "Package already has a recurring configuration",
+        }
+    }
 }
 
 // --- Contract Events ---
 // Changed from #[contracttype] to #[contractevent]
 
-#[contractevent]
+/// v2 schema (adds `timestamp`); published under a new topic so indexers can tell versions apart.
+#[contractevent(topics = ["fund_v2"])]
 pub struct FundEvent {
     pub from: Address,
     pub token: Address,
     pub amount: i128,
+    pub beneficiary_org: Option<String>,
+    pub timestamp: u64,
 }
 
 #[contractevent]
+pub struct DonationEvent {
+    pub donor: Address,
+    pub token: Address,
+    pub amount: i128,
+    pub timestamp: u64,
+}
+
+/// v2 schema (adds `created_at` and `expires_at`) so indexers can rebuild packages from
+/// events alone; published under a new topic so indexers can tell versions apart.
+#[contractevent(topics = ["package_created_v2"])]
 pub struct PackageCreatedEvent {
     pub id: u64,
     pub recipient: Address,
     pub amount: i128,
+    pub created_at: u64,
+    pub expires_at: u64,
 }
 
 #[contractevent]
 pub struct ClaimedEvent {
     pub id: u64,
-    pub recipient: Address,
+    pub recipient: Address, // Received the funds
+    pub claimant: Address,  // Signed the claim: the recipient, a delegate, or a co-signed claimer
     pub amount: i128,
+    pub memo: String,
 }
 
 #[contractevent]
 pub struct DisbursedEvent {
+    pub id: u64,
+    pub admin: Address, // Executor of the disbursement
+    pub amount: i128,
+}
+
+#[contractevent]
+pub struct DisbursedToEvent {
     pub id: u64,
     pub admin: Address,
+    pub original_recipient: Address,
+    pub target: Address,
     pub amount: i128,
 }
 
@@ -89,13 +525,217 @@ pub struct RevokedEvent {
     pub amount: i128,
 }
 
+#[contractevent]
+pub struct RecipientCancelledEvent {
+    pub id: u64,
+    pub recipient: Address,
+}
+
+#[contractevent]
+pub struct PackageTransferredOutEvent {
+    pub id: u64,
+    pub target_escrow: Address,
+}
+
+#[contractevent]
+pub struct PackageExpiredEvent {
+    pub id: u64,
+    pub admin: Address,
+}
+
+#[contractevent]
+pub struct MilestoneReleasedEvent {
+    pub package_id: u64,
+    pub milestone_id: u32,
+}
+
+#[contractevent]
+pub struct PackageRecurredEvent {
+    pub id: u64,
+    pub new_id: u64,
+}
+
+#[contractevent]
+pub struct LockedMapRebalancedEvent {
+    pub token: Address,
+    pub old_locked: i128,
+    pub new_locked: i128,
+}
+
+#[contractevent]
+pub struct PackageSplitEvent {
+    pub source_id: u64,
+    pub new_ids: Vec<u64>,
+}
+
+#[contractevent]
+pub struct PackagesMergedEvent {
+    pub source_ids: Vec<u64>,
+    pub new_id: u64,
+    pub total_amount: i128,
+}
+
+/// Point-in-time view of the pool, keyed by token for the per-token figures.
+#[contractevent]
+pub struct HealthSnapshotEvent {
+    pub timestamp: u64,
+    pub admin: Address,
+    pub total_packages: u64,
+    pub active_packages: u64,
+    pub total_locked: Map<Address, i128>,
+    pub contract_balance: Map<Address, i128>,
+}
+
 #[contractevent]
 pub struct RefundedEvent {
     pub id: u64,
     pub admin: Address,
+    pub destination: Address,
+    pub amount: i128,
+}
+
+#[contractevent]
+pub struct AdminTransferProposedEvent {
+    pub admin: Address,
+    pub new_admin: Address,
+}
+
+#[contractevent]
+pub struct AdminTransferCompletedEvent {
+    pub old_admin: Address,
+    pub new_admin: Address,
+}
+
+#[contractevent]
+pub struct ProposalCreatedEvent {
+    pub proposal_id: u64,
+    pub proposer: Address,
+    pub action: AdminAction,
+}
+
+#[contractevent]
+pub struct ProposalApprovedEvent {
+    pub proposal_id: u64,
+    pub signer: Address,
+}
+
+#[contractevent]
+pub struct ProposalExecutedEvent {
+    pub proposal_id: u64,
+}
+
+#[contractevent]
+pub struct ContractUpgradedEvent {
+    pub old_hash: BytesN<32>, // All zeroes if the contract was never upgraded before
+    pub new_hash: BytesN<32>,
+    pub admin: Address,
+}
+
+#[contractevent]
+pub struct OperatorAddedEvent {
+    pub admin: Address,
+    pub operator: Address,
+}
+
+#[contractevent]
+pub struct OperatorRemovedEvent {
+    pub admin: Address,
+    pub operator: Address,
+}
+
+#[contractevent]
+pub struct TokenAddedEvent {
+    pub admin: Address,
+    pub token: Address,
+}
+
+#[contractevent]
+pub struct TokenRemovedEvent {
+    pub admin: Address,
+    pub token: Address,
+}
+
+#[contractevent]
+pub struct ContractPausedEvent {
+    pub admin: Address,
+    pub timestamp: u64,
+}
+
+#[contractevent]
+pub struct ContractUnpausedEvent {
+    pub admin: Address,
+    pub timestamp: u64,
+}
+
+#[contractevent]
+pub struct FeeCollectedEvent {
+    pub admin: Address,
+    pub token: Address,
+    pub amount: i128,
+}
+
+#[contractevent]
+pub struct PackageExpiryUpdatedEvent {
+    pub id: u64,
+    pub old_expires_at: u64,
+    pub new_expires_at: u64,
+}
+
+#[contractevent]
+pub struct RecipientReassignedEvent {
+    pub id: u64,
+    pub old_recipient: Address,
+    pub new_recipient: Address,
+}
+
+#[contractevent]
+pub struct PackageMetadataUpdatedEvent {
+    pub id: u64,
+    pub key: Symbol,
+}
+
+/// Carries the SHA-256 of the note rather than the note itself to keep events small.
+#[contractevent]
+pub struct PackageNoteSetEvent {
+    pub id: u64,
+    pub note_hash: BytesN<32>,
+}
+
+#[contractevent]
+pub struct PackageCategorySetEvent {
+    pub id: u64,
+    pub category: Symbol,
+}
+
+#[contractevent]
+pub struct PoolWithdrawnEvent {
+    pub admin: Address,
+    pub token: Address,
+    pub amount: i128,
+}
+
+#[contractevent]
+pub struct ExcessWithdrawnEvent {
+    pub admin: Address,
+    pub token: Address,
+    pub destination: Address,
     pub amount: i128,
 }
 
+#[contractevent]
+pub struct PackageAmountIncreasedEvent {
+    pub id: u64,
+    pub old_amount: i128,
+    pub new_amount: i128,
+}
+
+#[contractevent]
+pub struct PackageAmountDecreasedEvent {
+    pub id: u64,
+    pub old_amount: i128,
+    pub new_amount: i128,
+}
+
 #[contract]
 pub struct AidEscrow;
 
@@ -108,9 +748,20 @@ impl AidEscrow {
             return Err(Error::AlreadyInitialized);
         }
         env.storage().instance().set(&KEY_ADMIN, &admin);
+        env.storage()
+            .instance()
+            .set(&KEY_VERSION, &String::from_str(&env, CONTRACT_VERSION));
         Ok(())
     }
 
+    /// Implementation version recorded at `init` and updated by `upgrade`.
+    pub fn get_version(env: Env) -> String {
+        env.storage()
+            .instance()
+            .get(&KEY_VERSION)
+            .unwrap_or(String::from_str(&env, CONTRACT_VERSION))
+    }
+
     pub fn get_admin(env: Env) -> Result<Address, Error> {
         env.storage()
             .instance()
@@ -118,305 +769,4387 @@ impl AidEscrow {
             .ok_or(Error::NotInitialized)
     }
 
-    // --- Funding & Packages ---
+    /// Proposes `new_admin` as the next admin (step 1 of 2).
+    /// The current admin stays in control until `accept_admin` is called by `new_admin`,
+    /// which is possible once the admin transfer delay has elapsed.
+    /// Proposing again overwrites any previous pending admin and restarts the delay.
+    pub fn propose_admin_transfer(env: Env, new_admin: Address) -> Result<(), Error> {
+        let admin = Self::require_single_admin(&env)?;
+        Self::transfer_admin_internal(&env, admin, new_admin);
+        Ok(())
+    }
 
-    /// Funds the contract (Pool Model).
-    /// Transfers `amount` of `token` from `from` to this contract.
-    /// This increases the contract's balance, allowing new packages to be created.
-    pub fn fund(env: Env, token: Address, from: Address, amount: i128) -> Result<(), Error> {
-        if amount <= 0 {
-            return Err(Error::InvalidAmount);
-        }
-        from.require_auth();
+    /// Alias of `propose_admin_transfer`.
+    pub fn transfer_admin(env: Env, new_admin: Address) -> Result<(), Error> {
+        Self::propose_admin_transfer(env, new_admin)
+    }
 
-        // Perform transfer: From -> Contract
-        let token_client = token::Client::new(&env, &token);
-        token_client.transfer(&from, env.current_contract_address(), &amount);
+    /// Minimum time between `propose_admin_transfer` and `accept_admin`, giving
+    /// stakeholders a window to react to an unexpected transfer. Defaults to `0`.
+    pub fn set_admin_transfer_delay(env: Env, seconds: u64) -> Result<(), Error> {
+        let admin = Self::get_admin(env.clone())?;
+        admin.require_auth();
 
-        // Emit event
-        FundEvent {
-            from,
-            token,
-            amount,
+        env.storage().instance().set(&KEY_ADMIN_DELAY, &seconds);
+        Self::log_admin_action(&env, symbol_short!("adm_delay"), None, &admin, "");
+        Ok(())
+    }
+
+    pub fn get_admin_transfer_delay(env: Env) -> u64 {
+        env.storage().instance().get(&KEY_ADMIN_DELAY).unwrap_or(0)
+    }
+
+    /// `(pending_admin, earliest_accept_at)` for the in-flight transfer, if any.
+    pub fn get_admin_transfer_proposal(env: Env) -> Option<(Address, u64)> {
+        let pending: Address = env.storage().instance().get(&KEY_PENDING_ADMIN)?;
+        let proposed_at: u64 = env
+            .storage()
+            .instance()
+            .get(&KEY_ADMIN_PROPOSED_AT)
+            .unwrap_or(0);
+        let delay = Self::get_admin_transfer_delay(env);
+        Some((pending, proposed_at.saturating_add(delay)))
+    }
+
+    /// Pending admin accepts the role (step 2 of 2), making the transfer live.
+    pub fn accept_admin(env: Env) -> Result<(), Error> {
+        let old_admin = Self::get_admin(env.clone())?;
+        let (new_admin, earliest_accept_at) =
+            Self::get_admin_transfer_proposal(env.clone()).ok_or(Error::NoPendingAdmin)?;
+        new_admin.require_auth();
+        if env.ledger().timestamp() < earliest_accept_at {
+            return Err(Error::TransferDelayNotElapsed);
+        }
+
+        env.storage().instance().set(&KEY_ADMIN, &new_admin);
+        env.storage().instance().remove(&KEY_PENDING_ADMIN);
+        env.storage().instance().remove(&KEY_ADMIN_PROPOSED_AT);
+
+        Self::log_admin_action(&env, symbol_short!("acc_adm"), None, &new_admin, "");
+
+        AdminTransferCompletedEvent {
+            old_admin,
+            new_admin,
         }
         .publish(&env);
 
         Ok(())
     }
 
-    /// Creates a package with a specific ID.
-    /// Locks funds from the available pool (Contract Balance - Total Locked).
-    pub fn create_package(
-        env: Env,
-        id: u64,
-        recipient: Address,
-        amount: i128,
-        token: Address,
-        expires_at: u64,
-    ) -> Result<u64, Error> {
+    /// Current admin withdraws an in-flight transfer proposal.
+    pub fn cancel_admin_transfer(env: Env) -> Result<(), Error> {
         let admin = Self::get_admin(env.clone())?;
         admin.require_auth();
 
-        if amount <= 0 {
-            return Err(Error::InvalidAmount);
+        if !env.storage().instance().has(&KEY_PENDING_ADMIN) {
+            return Err(Error::NoPendingAdmin);
         }
+        env.storage().instance().remove(&KEY_PENDING_ADMIN);
+        env.storage().instance().remove(&KEY_ADMIN_PROPOSED_AT);
 
-        // 1. Check ID Uniqueness
-        let key = (symbol_short!("pkg"), id);
-        if env.storage().persistent().has(&key) {
-            return Err(Error::PackageIdExists);
-        }
+        Self::log_admin_action(&env, symbol_short!("xfer_cncl"), None, &admin, "");
+        Ok(())
+    }
 
-        // 2. Check Solvency (Available Balance vs Locked)
-        let token_client = token::Client::new(&env, &token);
-        let contract_balance = token_client.balance(&env.current_contract_address());
+    pub fn get_pending_admin(env: Env) -> Option<Address> {
+        env.storage().instance().get(&KEY_PENDING_ADMIN)
+    }
 
-        let mut locked_map: Map<Address, i128> = env
-            .storage()
+    /// Replaces the contract code with the already-uploaded `new_wasm_hash`, optionally
+    /// recording `new_version`. Storage is preserved. Requires a multisig proposal when
+    /// multisig is configured.
+    pub fn upgrade(
+        env: Env,
+        new_wasm_hash: BytesN<32>,
+        new_version: Option<String>,
+    ) -> Result<(), Error> {
+        let admin = Self::require_single_admin(&env)?;
+        Self::upgrade_internal(&env, admin, new_wasm_hash, new_version);
+        Ok(())
+    }
+
+    // --- Multisig ---
+
+    /// Requires `threshold` of `signers` to approve refunds, revocations, `disburse_to`,
+    /// pool withdrawals, admin transfers, upgrades, and signer changes. An empty `signers`
+    /// list with `threshold = 0` turns multisig off. Once configured, the signer set can
+    /// only be changed through a `SetMultisig` proposal.
+    pub fn set_multisig(env: Env, signers: Vec<Address>, threshold: u32) -> Result<(), Error> {
+        let admin = Self::require_single_admin(&env)?;
+        Self::set_multisig_internal(&env, admin, signers, threshold)
+    }
+
+    pub fn get_multisig_signers(env: Env) -> Vec<Address> {
+        env.storage()
             .instance()
-            .get(&KEY_TOTAL_LOCKED)
-            .unwrap_or(Map::new(&env));
-        let current_locked = locked_map.get(token.clone()).unwrap_or(0);
+            .get(&KEY_SIGNERS)
+            .unwrap_or(Vec::new(&env))
+    }
 
-        // Ensure we don't over-promise funds
-        if contract_balance < current_locked + amount {
-            return Err(Error::InsufficientFunds);
+    /// Number of approvals needed per proposal; `0` when multisig is off.
+    pub fn get_multisig_threshold(env: Env) -> u32 {
+        env.storage().instance().get(&KEY_THRESHOLD).unwrap_or(0)
+    }
+
+    /// Signer opens a proposal, counting as its first approval.
+    /// Executes immediately if the threshold is 1.
+    pub fn propose_action(
+        env: Env,
+        proposer: Address,
+        action: AdminAction,
+        params: ActionParams,
+    ) -> Result<u64, Error> {
+        if Self::get_multisig_threshold(env.clone()) == 0 {
+            return Err(Error::InvalidState);
         }
+        Self::require_signer(&env, &proposer)?;
 
-        // 3. Update Locked State
-        locked_map.set(token.clone(), current_locked + amount);
-        env.storage().instance().set(&KEY_TOTAL_LOCKED, &locked_map);
+        let proposal_id = Self::read_counter(&env, &KEY_PROP_COUNT) + 1;
+        Self::bump_counter(&env, &KEY_PROP_COUNT, 1);
 
-        // 4. Create Package
-        let created_at = env.ledger().timestamp();
-        let package = Package {
-            id,
-            recipient: recipient.clone(),
-            amount,
-            token: token.clone(),
-            status: PackageStatus::Created,
-            created_at,
-            expires_at,
-            metadata: Map::new(&env),
+        let proposal = Proposal {
+            id: proposal_id,
+            action,
+            params,
+            approvals: Vec::from_array(&env, [proposer.clone()]),
+            executed: false,
         };
 
-        env.storage().persistent().set(&key, &package);
-
-        // Emit Event
-        PackageCreatedEvent {
-            id,
-            recipient,
-            amount,
+        ProposalCreatedEvent {
+            proposal_id,
+            proposer,
+            action,
         }
         .publish(&env);
 
-        Ok(id)
+        Self::store_or_execute_proposal(&env, proposal)?;
+        Ok(proposal_id)
     }
 
-    // --- Recipient Actions ---
+    /// Signer approves a pending proposal. The action runs as soon as approvals
+    /// reach the threshold; if it fails, the approval is rolled back with it.
+    pub fn approve_proposal(env: Env, signer: Address, proposal_id: u64) -> Result<(), Error> {
+        Self::require_signer(&env, &signer)?;
 
-    /// Recipient claims the package.
-    pub fn claim(env: Env, id: u64) -> Result<(), Error> {
-        let key = (symbol_short!("pkg"), id);
-        let mut package: Package = env
+        let key = (symbol_short!("prop"), proposal_id);
+        let mut proposal: Proposal = env
             .storage()
             .persistent()
             .get(&key)
-            .ok_or(Error::PackageNotFound)?;
+            .ok_or(Error::ProposalNotFound)?;
 
-        // Validations
-        if package.status != PackageStatus::Created {
-            return Err(Error::PackageNotActive);
+        if proposal.executed {
+            return Err(Error::InvalidState);
         }
-        // Check expiry
-        if package.expires_at > 0 && env.ledger().timestamp() > package.expires_at {
-            // Auto-expire if accessed after date
-            package.status = PackageStatus::Expired;
-            env.storage().persistent().set(&key, &package);
-            return Err(Error::PackageExpired);
+        if proposal.approvals.contains(&signer) {
+            return Err(Error::AlreadyApproved);
         }
+        proposal.approvals.push_back(signer.clone());
 
-        // Auth
-        package.recipient.require_auth();
+        ProposalApprovedEvent {
+            proposal_id,
+            signer,
+        }
+        .publish(&env);
 
-        // State Transition: Created -> Claimed
-        // Checks passed, update state FIRST (Re-entrancy protection)
-        package.status = PackageStatus::Claimed;
-        env.storage().persistent().set(&key, &package);
+        Self::store_or_execute_proposal(&env, proposal)
+    }
+
+    pub fn get_proposal(env: Env, proposal_id: u64) -> Result<Proposal, Error> {
+        env.storage()
+            .persistent()
+            .get(&(symbol_short!("prop"), proposal_id))
+            .ok_or(Error::ProposalNotFound)
+    }
 
-        // Update Global Locked
-        Self::decrement_locked(&env, &package.token, package.amount);
+    // --- Emergency Pause ---
 
-        // Effect: Transfer Funds
-        let token_client = token::Client::new(&env, &package.token);
-        token_client.transfer(
-            &env.current_contract_address(),
-            &package.recipient,
-            &package.amount,
-        );
+    /// Halts every state-changing entry point until `unpause` is called.
+    pub fn pause(env: Env) -> Result<(), Error> {
+        let admin = Self::get_admin(env.clone())?;
+        admin.require_auth();
 
-        // Emit Event
-        ClaimedEvent {
-            id,
-            recipient: package.recipient.clone(),
-            amount: package.amount,
+        env.storage().instance().set(&KEY_PAUSED, &true);
+
+        Self::log_admin_action(&env, symbol_short!("pause"), None, &admin, "");
+
+        ContractPausedEvent {
+            admin,
+            timestamp: env.ledger().timestamp(),
         }
         .publish(&env);
 
         Ok(())
     }
 
-    // --- Admin Actions ---
-
-    /// Admin manually triggers disbursement (overrides recipient claim need, strictly checks status).
-    pub fn disburse(env: Env, id: u64) -> Result<(), Error> {
+    pub fn unpause(env: Env) -> Result<(), Error> {
         let admin = Self::get_admin(env.clone())?;
         admin.require_auth();
 
-        let key = (symbol_short!("pkg"), id);
-        let mut package: Package = env
-            .storage()
-            .persistent()
-            .get(&key)
-            .ok_or(Error::PackageNotFound)?;
+        env.storage().instance().set(&KEY_PAUSED, &false);
 
-        if package.status != PackageStatus::Created {
-            return Err(Error::PackageNotActive);
+        Self::log_admin_action(&env, symbol_short!("unpause"), None, &admin, "");
+
+        ContractUnpausedEvent {
+            admin,
+            timestamp: env.ledger().timestamp(),
         }
+        .publish(&env);
 
-        // State Transition
-        package.status = PackageStatus::Claimed; // Mark as claimed (or Disbursed if we had that enum)
-        env.storage().persistent().set(&key, &package);
+        Ok(())
+    }
 
-        // Update Locked
-        Self::decrement_locked(&env, &package.token, package.amount);
+    pub fn is_paused(env: Env) -> bool {
+        env.storage().instance().get(&KEY_PAUSED).unwrap_or(false)
+    }
 
-        // Transfer
-        let token_client = token::Client::new(&env, &package.token);
-        token_client.transfer(
-            &env.current_contract_address(),
-            &package.recipient,
-            &package.amount,
-        );
+    // --- Operators ---
 
-        DisbursedEvent {
-            id,
-            admin: admin.clone(),
-            amount: package.amount,
-        }
-        .publish(&env);
+    /// Grants `operator` the right to create packages.
+    /// Operators cannot disburse, revoke, or refund.
+    pub fn add_operator(env: Env, operator: Address) -> Result<(), Error> {
+        let admin = Self::get_admin(env.clone())?;
+        admin.require_auth();
+
+        let mut ops = Self::operators_map(&env);
+        ops.set(operator.clone(), true);
+        env.storage().persistent().set(&KEY_OPERATORS, &ops);
+
+        Self::log_admin_action(&env, symbol_short!("add_op"), None, &admin, "");
+
+        OperatorAddedEvent { admin, operator }.publish(&env);
 
         Ok(())
     }
 
-    /// Admin revokes a package (Cancels it). Funds are effectively unlocked but remain in contract pool.
-    pub fn revoke(env: Env, id: u64) -> Result<(), Error> {
+    /// Revokes a previously granted operator role. No-op if `operator` is not registered.
+    pub fn remove_operator(env: Env, operator: Address) -> Result<(), Error> {
         let admin = Self::get_admin(env.clone())?;
         admin.require_auth();
 
-        let key = (symbol_short!("pkg"), id);
-        let mut package: Package = env
-            .storage()
-            .persistent()
-            .get(&key)
-            .ok_or(Error::PackageNotFound)?;
+        let mut ops = Self::operators_map(&env);
+        if ops.remove(operator.clone()).is_some() {
+            env.storage().persistent().set(&KEY_OPERATORS, &ops);
+            OperatorRemovedEvent {
+                admin: admin.clone(),
+                operator,
+            }
+            .publish(&env);
+        }
 
-        if package.status != PackageStatus::Created {
-            return Err(Error::InvalidState);
+        Self::log_admin_action(&env, symbol_short!("rm_op"), None, &admin, "");
+        Ok(())
+    }
+
+    pub fn is_operator(env: Env, address: Address) -> bool {
+        Self::operators_map(&env).get(address).unwrap_or(false)
+    }
+
+    pub fn get_operators(env: Env) -> Vec<Address> {
+        let mut out = Vec::new(&env);
+        for (operator, active) in Self::operators_map(&env).iter() {
+            if active {
+                out.push_back(operator);
+            }
         }
+        out
+    }
 
-        // State Transition
-        package.status = PackageStatus::Cancelled;
-        env.storage().persistent().set(&key, &package);
+    // --- Token Whitelist ---
 
-        // Unlock funds (return to pool)
-        Self::decrement_locked(&env, &package.token, package.amount);
+    /// When enabled, `fund` and `create_package` only accept whitelisted tokens.
+    pub fn set_token_whitelist_enabled(env: Env, enabled: bool) -> Result<(), Error> {
+        let admin = Self::get_admin(env.clone())?;
+        admin.require_auth();
 
-        RevokedEvent {
-            id,
+        env.storage()
+            .instance()
+            .set(&KEY_TOKEN_WL_ENABLED, &enabled);
+        Self::log_admin_action(&env, symbol_short!("tok_wl"), None, &admin, "");
+        Ok(())
+    }
+
+    pub fn add_allowed_token(env: Env, token: Address) -> Result<(), Error> {
+        let admin = Self::get_admin(env.clone())?;
+        admin.require_auth();
+
+        let mut whitelist = Self::token_whitelist(&env);
+        whitelist.set(token.clone(), true);
+        env.storage()
+            .instance()
+            .set(&KEY_TOKEN_WHITELIST, &whitelist);
+
+        TokenAddedEvent {
             admin: admin.clone(),
-            amount: package.amount,
+            token,
         }
         .publish(&env);
 
+        Self::log_admin_action(&env, symbol_short!("add_tok"), None, &admin, "");
         Ok(())
     }
 
-    pub fn refund(env: Env, id: u64) -> Result<(), Error> {
+    pub fn remove_allowed_token(env: Env, token: Address) -> Result<(), Error> {
         let admin = Self::get_admin(env.clone())?;
         admin.require_auth();
 
-        let key = (symbol_short!("pkg"), id);
-        let mut package: Package = env
-            .storage()
-            .persistent()
-            .get(&key)
-            .ok_or(Error::PackageNotFound)?;
-
-        // Can only refund if Expired or Cancelled.
-        // If Created, must Revoke first. If Claimed, impossible.
-        // If Refunded, impossible.
-        if package.status == PackageStatus::Created {
-            // Check if actually expired
-            if package.expires_at > 0 && env.ledger().timestamp() > package.expires_at {
-                package.status = PackageStatus::Expired;
-                // If we just expired it, we need to unlock the funds first
-                Self::decrement_locked(&env, &package.token, package.amount);
-            } else {
-                return Err(Error::InvalidState); // Must revoke first
+        let mut whitelist = Self::token_whitelist(&env);
+        if whitelist.remove(token.clone()).is_some() {
+            env.storage()
+                .instance()
+                .set(&KEY_TOKEN_WHITELIST, &whitelist);
+            TokenRemovedEvent {
+                admin: admin.clone(),
+                token,
             }
-        } else if package.status == PackageStatus::Claimed
-            || package.status == PackageStatus::Refunded
-        {
-            return Err(Error::InvalidState);
+            .publish(&env);
         }
 
-        // If Cancelled, funds were already unlocked in `revoke`.
-        // If Expired (logic above), funds were just unlocked.
+        Self::log_admin_action(&env, symbol_short!("rm_tok"), None, &admin, "");
+        Ok(())
+    }
 
-        // State Transition
-        package.status = PackageStatus::Refunded;
-        env.storage().persistent().set(&key, &package);
+    /// Returns whether `token` may be used. Every token is allowed while the whitelist is disabled.
+    pub fn is_token_allowed(env: Env, token: Address) -> bool {
+        let enabled: bool = env
+            .storage()
+            .instance()
+            .get(&KEY_TOKEN_WL_ENABLED)
+            .unwrap_or(false);
+        !enabled || Self::token_whitelist(&env).get(token).unwrap_or(false)
+    }
 
-        // Transfer Contract -> Admin
-        let token_client = token::Client::new(&env, &package.token);
-        token_client.transfer(&env.current_contract_address(), &admin, &package.amount);
+    // --- Fees ---
 
-        RefundedEvent {
-            id,
-            admin: admin.clone(),
-            amount: package.amount,
+    /// Sets the fee (in basis points) deducted from every claim/disbursement of `token`.
+    /// Capped at `MAX_FEE_RATE_BPS` (10%).
+    pub fn set_fee_rate_bps(env: Env, token: Address, rate_bps: u32) -> Result<(), Error> {
+        let admin = Self::get_admin(env.clone())?;
+        admin.require_auth();
+
+        if rate_bps > MAX_FEE_RATE_BPS {
+            return Err(Error::InvalidFeeRate);
         }
-        .publish(&env);
 
+        let mut rates: Map<Address, u32> = env
+            .storage()
+            .instance()
+            .get(&KEY_FEE_RATES)
+            .unwrap_or(Map::new(&env));
+        rates.set(token, rate_bps);
+        env.storage().instance().set(&KEY_FEE_RATES, &rates);
+
+        Self::log_admin_action(&env, symbol_short!("fee_rate"), None, &admin, "");
         Ok(())
     }
 
-    // --- Helpers ---
+    pub fn get_fee_rate_bps(env: Env, token: Address) -> u32 {
+        let rates: Map<Address, u32> = env
+            .storage()
+            .instance()
+            .get(&KEY_FEE_RATES)
+            .unwrap_or(Map::new(&env));
+        rates.get(token).unwrap_or(0)
+    }
 
-    fn decrement_locked(env: &Env, token: &Address, amount: i128) {
-        let mut locked_map: Map<Address, i128> = env
+    pub fn get_fees_accrued(env: Env, token: Address) -> i128 {
+        let fees: Map<Address, i128> = env
             .storage()
             .instance()
-            .get(&KEY_TOTAL_LOCKED)
-            .unwrap_or(Map::new(env));
+            .get(&KEY_FEES_ACCRUED)
+            .unwrap_or(Map::new(&env));
+        fees.get(token).unwrap_or(0)
+    }
 
-        let current = locked_map.get(token.clone()).unwrap_or(0);
-        let new_locked = if current > amount {
-            current - amount
-        } else {
-            0
-        };
+    /// Admin drains the accrued fees for `token` to themselves.
+    /// Accrued fees stay in the locked total until collected, so they can never back new packages.
+    pub fn collect_fees(env: Env, token: Address) -> Result<(), Error> {
+        let admin = Self::get_admin(env.clone())?;
+        admin.require_auth();
 
-        locked_map.set(token.clone(), new_locked);
-        env.storage().instance().set(&KEY_TOTAL_LOCKED, &locked_map);
+        let mut fees: Map<Address, i128> = env
+            .storage()
+            .instance()
+            .get(&KEY_FEES_ACCRUED)
+            .unwrap_or(Map::new(&env));
+        let amount = fees.get(token.clone()).unwrap_or(0);
+        if amount == 0 {
+            return Ok(());
+        }
+
+        fees.set(token.clone(), 0);
+        env.storage().instance().set(&KEY_FEES_ACCRUED, &fees);
+        Self::decrement_locked(&env, &token, amount);
+
+        let token_client = token::Client::new(&env, &token);
+        token_client.transfer(&env.current_contract_address(), &admin, &amount);
+
+        Self::log_admin_action(&env, symbol_short!("coll_fees"), None, &admin, "");
+
+        FeeCollectedEvent {
+            admin,
+            token,
+            amount,
+        }
+        .publish(&env);
+
+        Ok(())
     }
 
-    pub fn get_package(env: Env, id: u64) -> Result<Package, Error> {
-        let key = (symbol_short!("pkg"), id);
-        env.storage()
-            .persistent()
+    // --- Package Amount Limits ---
+
+    /// Sets the smallest package amount allowed for `token` (`0` disables the floor).
+    pub fn set_min_package_amount(env: Env, token: Address, min: i128) -> Result<(), Error> {
+        let admin = Self::get_admin(env.clone())?;
+        admin.require_auth();
+
+        if min < 0 {
+            return Err(Error::InvalidAmount);
+        }
+        Self::set_token_limit(&env, &KEY_MIN_AMOUNTS, token, min);
+        Self::log_admin_action(&env, symbol_short!("min_amt"), None, &admin, "");
+        Ok(())
+    }
+
+    /// Sets the largest package amount allowed for `token` (`0` disables the cap).
+    pub fn set_max_package_amount(env: Env, token: Address, max: i128) -> Result<(), Error> {
+        let admin = Self::get_admin(env.clone())?;
+        admin.require_auth();
+
+        if max < 0 {
+            return Err(Error::InvalidAmount);
+        }
+        Self::set_token_limit(&env, &KEY_MAX_AMOUNTS, token, max);
+        Self::log_admin_action(&env, symbol_short!("max_amt"), None, &admin, "");
+        Ok(())
+    }
+
+    /// Returns `(min, max)` package amounts for `token`; `0` means unconfigured.
+    pub fn get_token_limits(env: Env, token: Address) -> (i128, i128) {
+        let mins: Map<Address, i128> = env
+            .storage()
+            .instance()
+            .get(&KEY_MIN_AMOUNTS)
+            .unwrap_or(Map::new(&env));
+        let maxs: Map<Address, i128> = env
+            .storage()
+            .instance()
+            .get(&KEY_MAX_AMOUNTS)
+            .unwrap_or(Map::new(&env));
+        (
+            mins.get(token.clone()).unwrap_or(0),
+            maxs.get(token).unwrap_or(0),
+        )
+    }
+
+    // --- Recipient Allowlist ---
+
+    /// When enabled, packages can only be created for (or reassigned to) allowlisted recipients.
+    pub fn set_allowlist_mode(env: Env, enabled: bool) -> Result<(), Error> {
+        let admin = Self::get_admin(env.clone())?;
+        admin.require_auth();
+
+        env.storage().instance().set(&KEY_ALLOWLIST_MODE, &enabled);
+        Self::log_admin_action(&env, symbol_short!("al_mode"), None, &admin, "");
+        Ok(())
+    }
+
+    pub fn add_recipient_to_allowlist(env: Env, recipient: Address) -> Result<(), Error> {
+        let admin = Self::get_admin(env.clone())?;
+        admin.require_auth();
+
+        Self::store_index(&env, &(KEY_ALLOWLIST, recipient), &true);
+        Self::log_admin_action(&env, symbol_short!("al_add"), None, &admin, "");
+        Ok(())
+    }
+
+    pub fn remove_recipient_from_allowlist(env: Env, recipient: Address) -> Result<(), Error> {
+        let admin = Self::get_admin(env.clone())?;
+        admin.require_auth();
+
+        env.storage()
+            .persistent()
+            .remove(&(KEY_ALLOWLIST, recipient));
+        Self::log_admin_action(&env, symbol_short!("al_rm"), None, &admin, "");
+        Ok(())
+    }
+
+    /// Returns whether `recipient` may receive packages. Everyone is allowed while the mode is off.
+    pub fn is_recipient_allowed(env: Env, recipient: Address) -> bool {
+        let enabled: bool = env
+            .storage()
+            .instance()
+            .get(&KEY_ALLOWLIST_MODE)
+            .unwrap_or(false);
+        !enabled
+            || env
+                .storage()
+                .persistent()
+                .get(&(KEY_ALLOWLIST, recipient))
+                .unwrap_or(false)
+    }
+
+    // --- Recipient Package Limit ---
+
+    /// Caps how many active packages one recipient may hold (`0` = unlimited).
+    pub fn set_max_packages_per_recipient(env: Env, max: u32) -> Result<(), Error> {
+        let admin = Self::get_admin(env.clone())?;
+        admin.require_auth();
+
+        env.storage().instance().set(&KEY_MAX_RECIPIENT_PKGS, &max);
+        Self::log_admin_action(&env, symbol_short!("max_rpkgs"), None, &admin, "");
+        Ok(())
+    }
+
+    pub fn get_max_packages_per_recipient(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&KEY_MAX_RECIPIENT_PKGS)
+            .unwrap_or(0)
+    }
+
+    /// Number of active packages currently held by `recipient`.
+    /// (Named to fit Soroban's 32-character function name limit.)
+    pub fn get_recipient_active_pkg_count(env: Env, recipient: Address) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&(KEY_RECIPIENT_PKG_COUNT, recipient))
+            .unwrap_or(0)
+    }
+
+    /// IDs of `recipient`'s active packages, read from an index kept up to date on every
+    /// creation, reassignment, and terminal transition.
+    /// (Named to fit Soroban's 32-character function name limit.)
+    pub fn get_recipient_active_pkgs(env: Env, recipient: Address) -> Vec<u64> {
+        Self::recipient_active_ids(&env, &recipient)
+    }
+
+    /// Every package ever created for (or reassigned to) `recipient`, oldest first.
+    pub fn get_packages_for_recipient(
+        env: Env,
+        recipient: Address,
+        offset: u32,
+        limit: u32,
+    ) -> Vec<u64> {
+        Self::id_list_slice(&env, &(KEY_RECIPIENT_PKGS, recipient), offset, limit)
+    }
+
+    /// Total packages (active and historical) listed for `recipient`.
+    pub fn get_recipient_package_count(env: Env, recipient: Address) -> u32 {
+        Self::id_list_len(&env, &(KEY_RECIPIENT_PKGS, recipient))
+    }
+
+    // --- KYC ---
+
+    /// Sets the address trusted to attest that recipients passed KYC.
+    pub fn set_kyc_attestor(env: Env, attestor: Address) -> Result<(), Error> {
+        let admin = Self::get_admin(env.clone())?;
+        admin.require_auth();
+
+        env.storage().instance().set(&KEY_KYC_ATTESTOR, &attestor);
+        Self::log_admin_action(&env, symbol_short!("kyc_att"), None, &admin, "");
+        Ok(())
+    }
+
+    pub fn get_kyc_attestor(env: Env) -> Option<Address> {
+        env.storage().instance().get(&KEY_KYC_ATTESTOR)
+    }
+
+    /// Admin marks whether claiming package `id` requires a KYC-attested recipient.
+    pub fn set_package_require_kyc(env: Env, id: u64, required: bool) -> Result<(), Error> {
+        let admin = Self::get_admin(env.clone())?;
+        admin.require_auth();
+        Self::get_package(env.clone(), id)?;
+
+        let key = (symbol_short!("kyc"), id);
+        if required {
+            env.storage().persistent().set(&key, &true);
+        } else {
+            env.storage().persistent().remove(&key);
+        }
+        Self::log_admin_action(&env, symbol_short!("kyc_req"), Some(id), &admin, "");
+        Ok(())
+    }
+
+    pub fn package_requires_kyc(env: Env, id: u64) -> bool {
+        env.storage()
+            .persistent()
+            .get(&(symbol_short!("kyc"), id))
+            .unwrap_or(false)
+    }
+
+    /// The configured attestor records that `recipient` passed KYC.
+    pub fn attest_recipient(env: Env, recipient: Address) -> Result<(), Error> {
+        let attestor = Self::get_kyc_attestor(env.clone()).ok_or(Error::NotAuthorized)?;
+        attestor.require_auth();
+
+        Self::store_index(&env, &(KEY_KYC, recipient), &true);
+        Ok(())
+    }
+
+    /// The configured attestor withdraws a stale or mistaken attestation. Claims on
+    /// KYC-gated packages fail again until `recipient` is re-attested.
+    pub fn revoke_kyc_attestation(env: Env, recipient: Address) -> Result<(), Error> {
+        let attestor = Self::get_kyc_attestor(env.clone()).ok_or(Error::NotAuthorized)?;
+        attestor.require_auth();
+
+        env.storage().persistent().remove(&(KEY_KYC, recipient));
+        Ok(())
+    }
+
+    pub fn is_kyc_attested(env: Env, recipient: Address) -> bool {
+        env.storage()
+            .persistent()
+            .get(&(KEY_KYC, recipient))
+            .unwrap_or(false)
+    }
+
+    // --- Recipient Blacklist ---
+
+    /// Blocks `recipient` from receiving new packages and from claiming existing ones.
+    pub fn blacklist_recipient(env: Env, recipient: Address) -> Result<(), Error> {
+        let admin = Self::get_admin(env.clone())?;
+        admin.require_auth();
+
+        Self::store_index(&env, &(KEY_BLACKLIST, recipient), &true);
+        Self::log_admin_action(&env, symbol_short!("blacklist"), None, &admin, "");
+        Ok(())
+    }
+
+    pub fn unblacklist_recipient(env: Env, recipient: Address) -> Result<(), Error> {
+        let admin = Self::get_admin(env.clone())?;
+        admin.require_auth();
+
+        env.storage()
+            .persistent()
+            .remove(&(KEY_BLACKLIST, recipient));
+        Self::log_admin_action(&env, symbol_short!("unblklst"), None, &admin, "");
+        Ok(())
+    }
+
+    pub fn is_blacklisted(env: Env, recipient: Address) -> bool {
+        env.storage()
+            .persistent()
+            .get(&(KEY_BLACKLIST, recipient))
+            .unwrap_or(false)
+    }
+
+    /// Human-readable message for `err`, so clients need not bundle their own table.
+    pub fn error_description(env: Env, err: Error) -> String {
+        String::from_str(&env, err.description())
+    }
+
+    // --- Funding & Packages ---
+
+    /// Funds the contract (Pool Model).
+    /// Transfers `amount` of `token` from `from` to this contract.
+    /// This increases the contract's balance, allowing new packages to be created.
+    pub fn fund(env: Env, token: Address, from: Address, amount: i128) -> Result<(), Error> {
+        Self::fund_internal(&env, token, from, amount, None)
+    }
+
+    /// Same as `fund`, but credits the deposit to `beneficiary_org` for donor reporting.
+    /// Pool accounting is identical; the tag only feeds `get_funding_by_org`.
+    pub fn fund_on_behalf(
+        env: Env,
+        token: Address,
+        funder: Address,
+        beneficiary_org: String,
+        amount: i128,
+    ) -> Result<(), Error> {
+        Self::fund_internal(&env, token, funder, amount, Some(beneficiary_org))
+    }
+
+    /// Cumulative amount funded under `org` via `fund_on_behalf` (summed across tokens).
+    pub fn get_funding_by_org(env: Env, org: String) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&(KEY_FUND_ATTRIBUTION, org))
+            .unwrap_or(0)
+    }
+
+    fn fund_internal(
+        env: &Env,
+        token: Address,
+        from: Address,
+        amount: i128,
+        beneficiary_org: Option<String>,
+    ) -> Result<(), Error> {
+        Self::deposit(env, &token, &from, amount)?;
+
+        if let Some(org) = beneficiary_org.clone() {
+            let key = (KEY_FUND_ATTRIBUTION, org);
+            let current: i128 = env.storage().persistent().get(&key).unwrap_or(0);
+            Self::store_index(env, &key, &(current + amount));
+        }
+
+        // Emit event
+        FundEvent {
+            from,
+            token,
+            amount,
+            beneficiary_org,
+            timestamp: env.ledger().timestamp(),
+        }
+        .publish(env);
+
+        Ok(())
+    }
+
+    /// Public donation into the pool. Moves funds exactly like `fund`, but emits a
+    /// `DonationEvent` and is tracked in the on-chain donor ledger.
+    pub fn donate_to_pool(
+        env: Env,
+        token: Address,
+        donor: Address,
+        amount: i128,
+    ) -> Result<(), Error> {
+        Self::deposit(&env, &token, &donor, amount)?;
+        Self::add_to_total(&env, &KEY_TOTAL_DONATIONS, &token, amount);
+
+        let key = (KEY_DONOR_LEDGER, donor.clone(), token.clone());
+        let current: i128 = env.storage().persistent().get(&key).unwrap_or(0);
+        Self::store_index(&env, &key, &(current + amount));
+
+        DonationEvent {
+            donor,
+            token,
+            amount,
+            timestamp: env.ledger().timestamp(),
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Cumulative `token` donations made through `donate_to_pool`.
+    pub fn get_total_donations(env: Env, token: Address) -> i128 {
+        Self::read_totals(&env, &KEY_TOTAL_DONATIONS)
+            .get(token)
+            .unwrap_or(0)
+    }
+
+    /// Cumulative `token` donations made by `donor`.
+    pub fn get_donor_amount(env: Env, donor: Address, token: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&(KEY_DONOR_LEDGER, donor, token))
+            .unwrap_or(0)
+    }
+
+    /// Validates and moves `amount` of `token` from `from` into the pool.
+    fn deposit(env: &Env, token: &Address, from: &Address, amount: i128) -> Result<(), Error> {
+        Self::check_deposit(env, token, amount)?;
+        from.require_auth();
+
+        // Perform transfer: From -> Contract
+        let token_client = token::Client::new(env, token);
+        token_client.transfer(from, env.current_contract_address(), &amount);
+        Self::add_to_total(env, &KEY_TOTAL_FUNDED, token, amount);
+        Ok(())
+    }
+
+    fn check_deposit(env: &Env, token: &Address, amount: i128) -> Result<(), Error> {
+        Self::require_not_paused(env)?;
+        if amount <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+        if !Self::is_token_allowed(env.clone(), token.clone()) {
+            return Err(Error::TokenNotAllowed);
+        }
+        Ok(())
+    }
+
+    /// Funding entry point for other contracts (DAO treasuries, multisig wallets).
+    /// Pulls `amount` with `transfer_from`, so before calling, `funder_contract` must
+    /// `approve` this contract as spender of at least `amount` on `token`.
+    /// When the funder invokes this directly, its `require_auth` is satisfied implicitly.
+    pub fn fund_from_contract(
+        env: Env,
+        token: Address,
+        funder_contract: Address,
+        amount: i128,
+    ) -> Result<(), Error> {
+        Self::check_deposit(&env, &token, amount)?;
+        funder_contract.require_auth();
+
+        let this = env.current_contract_address();
+        token::Client::new(&env, &token).transfer_from(&this, &funder_contract, &this, &amount);
+        Self::add_to_total(&env, &KEY_TOTAL_FUNDED, &token, amount);
+
+        FundEvent {
+            from: funder_contract,
+            token,
+            amount,
+            beneficiary_org: None,
+            timestamp: env.ledger().timestamp(),
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Creates a package with a specific ID.
+    /// Locks funds from the available pool (Contract Balance - Total Locked).
+    /// `caller` must be the admin or a registered operator.
+    /// The package cannot be claimed before `start_at` (`0` = immediately).
+    /// `external_ref` links the package to an off-chain ID and must be unique.
+    /// With a `condition`, claims additionally require the oracle check to pass.
+    /// With auto-increment on, `id` must be `0` and the contract assigns the next free ID.
+    /// Returns the ID the package was stored under.
+    pub fn create_package(
+        env: Env,
+        caller: Address,
+        id: u64,
+        recipient: Address,
+        amount: i128,
+        token: Address,
+        expires_at: u64,
+        start_at: u64,
+        external_ref: Option<String>,
+        condition: Option<OracleCondition>,
+    ) -> Result<u64, Error> {
+        Self::require_not_paused(&env)?;
+        Self::require_creator(&env, &caller)?;
+
+        let id = Self::resolve_package_id(&env, id)?;
+        Self::create_package_internal(
+            &env,
+            &caller,
+            id,
+            recipient,
+            amount,
+            token,
+            expires_at,
+            start_at,
+            Map::new(&env),
+            external_ref.unwrap_or(String::from_str(&env, "")),
+        )?;
+
+        if let Some(condition) = condition {
+            env.storage()
+                .persistent()
+                .set(&(symbol_short!("cond"), id), &condition);
+        }
+
+        Self::log_admin_action(&env, symbol_short!("create"), Some(id), &caller, "");
+        Ok(id)
+    }
+
+    /// When enabled, `create_package`, `create_package_and_fund`, and `create_package_idempotent`
+    /// reject caller-chosen IDs (pass `0`) and assign the next free ID themselves.
+    pub fn set_auto_increment_enabled(env: Env, enabled: bool) -> Result<(), Error> {
+        let admin = Self::get_admin(env.clone())?;
+        admin.require_auth();
+
+        env.storage().instance().set(&KEY_AUTO_INCREMENT, &enabled);
+        Self::log_admin_action(&env, symbol_short!("auto_inc"), None, &admin, "");
+        Ok(())
+    }
+
+    pub fn is_auto_increment_enabled(env: Env) -> bool {
+        env.storage()
+            .instance()
+            .get(&KEY_AUTO_INCREMENT)
+            .unwrap_or(false)
+    }
+
+    /// The ID the next auto-incremented (or recurring) package will receive.
+    pub fn get_next_package_id(env: Env) -> u64 {
+        Self::next_package_id(&env)
+    }
+
+    /// Deposits `amount` from `from` and locks it in a new package in one call.
+    /// `from` must be the admin or an operator. Atomic: if either half fails the whole
+    /// call reverts, so funds never land in the pool without the package being created.
+    /// Emits both `FundEvent` and `PackageCreatedEvent`.
+    pub fn create_package_and_fund(
+        env: Env,
+        id: u64,
+        recipient: Address,
+        amount: i128,
+        token: Address,
+        from: Address,
+        expires_at: u64,
+    ) -> Result<u64, Error> {
+        let admin = Self::get_admin(env.clone())?;
+        if from != admin && !Self::is_operator(env.clone(), from.clone()) {
+            return Err(Error::NotAuthorized);
+        }
+        // `fund_internal` takes `from`'s signature for both halves
+        Self::fund_internal(&env, token.clone(), from.clone(), amount, None)?;
+
+        let id = Self::resolve_package_id(&env, id)?;
+        Self::create_package_internal(
+            &env,
+            &from,
+            id,
+            recipient,
+            amount,
+            token,
+            expires_at,
+            0,
+            Map::new(&env),
+            String::from_str(&env, ""),
+        )?;
+
+        Self::log_admin_action(&env, symbol_short!("create"), Some(id), &from, "");
+        Ok(id)
+    }
+
+    /// Retry-safe `create_package` for the admin: the first call with a given `nonce` creates
+    /// the package, later calls with the same nonce return the original ID without side effects.
+    pub fn create_package_idempotent(
+        env: Env,
+        nonce: BytesN<32>,
+        id: u64,
+        recipient: Address,
+        amount: i128,
+        token: Address,
+        expires_at: u64,
+    ) -> Result<u64, Error> {
+        Self::require_not_paused(&env)?;
+        let admin = Self::get_admin(env.clone())?;
+        admin.require_auth();
+
+        let nonce_key = (KEY_NONCES, nonce);
+        if let Some(existing_id) = env.storage().persistent().get(&nonce_key) {
+            return Ok(existing_id);
+        }
+
+        let id = Self::resolve_package_id(&env, id)?;
+        Self::create_package_internal(
+            &env,
+            &admin,
+            id,
+            recipient,
+            amount,
+            token,
+            expires_at,
+            0,
+            Map::new(&env),
+            String::from_str(&env, ""),
+        )?;
+
+        Self::store_index(&env, &nonce_key, &id);
+        Self::log_admin_action(&env, symbol_short!("create"), Some(id), &admin, "");
+        Ok(id)
+    }
+
+    /// Creates `new_id` for `new_recipient`/`new_amount`, copying token, expiry window, and
+    /// metadata from `source_id`. The source package may be in any state.
+    pub fn clone_package(
+        env: Env,
+        source_id: u64,
+        new_id: u64,
+        new_recipient: Address,
+        new_amount: i128,
+    ) -> Result<u64, Error> {
+        Self::require_not_paused(&env)?;
+        let admin = Self::get_admin(env.clone())?;
+        admin.require_auth();
+
+        let source = Self::get_package(env.clone(), source_id)?;
+
+        Self::create_package_internal(
+            &env,
+            &admin,
+            new_id,
+            new_recipient,
+            new_amount,
+            source.token,
+            source.expires_at,
+            source.start_at,
+            source.metadata,
+            String::from_str(&env, ""),
+        )?;
+
+        Self::log_admin_action(&env, symbol_short!("clone"), Some(new_id), &admin, "");
+        Ok(new_id)
+    }
+
+    /// Cancels an unclaimed package and re-issues its amount as one new package per
+    /// `(new_id, recipient, amount)` entry, keeping token, timing, and metadata.
+    /// The split amounts must add up to the source amount exactly.
+    pub fn split_package(
+        env: Env,
+        id: u64,
+        splits: Vec<(u64, Address, i128)>,
+    ) -> Result<Vec<u64>, Error> {
+        Self::require_not_paused(&env)?;
+        let admin = Self::get_admin(env.clone())?;
+        admin.require_auth();
+
+        let source = Self::get_package(env.clone(), id)?;
+        if source.status != PackageStatus::Created {
+            return Err(Error::InvalidState);
+        }
+        let total = splits.iter().try_fold(0i128, |total, (_, _, amount)| {
+            if amount <= 0 {
+                return Err(Error::InvalidAmount);
+            }
+            total.checked_add(amount).ok_or(Error::InvalidAmount)
+        })?;
+        if total != source.amount {
+            return Err(Error::InvalidAmount);
+        }
+
+        // Free the source amount first so the new packages can lock it again
+        let source = Self::retire_package(&env, &admin, id)?;
+
+        let mut new_ids = Vec::new(&env);
+        for (new_id, recipient, amount) in splits.iter() {
+            Self::create_package_internal(
+                &env,
+                &admin,
+                new_id,
+                recipient,
+                amount,
+                source.token.clone(),
+                source.expires_at,
+                source.start_at,
+                source.metadata.clone(),
+                String::from_str(&env, ""),
+            )?;
+            new_ids.push_back(new_id);
+        }
+
+        Self::log_admin_action(&env, symbol_short!("split"), Some(id), &admin, "");
+
+        PackageSplitEvent {
+            source_id: id,
+            new_ids: new_ids.clone(),
+        }
+        .publish(&env);
+
+        Ok(new_ids)
+    }
+
+    /// Cancels several unclaimed packages of `recipient` and re-issues their combined amount
+    /// as `new_id`. The new package keeps the latest deadline (no expiry wins), the latest
+    /// cliff, and the union of the source metadata.
+    pub fn merge_packages(
+        env: Env,
+        ids: Vec<u64>,
+        new_id: u64,
+        recipient: Address,
+    ) -> Result<u64, Error> {
+        Self::require_not_paused(&env)?;
+        let admin = Self::get_admin(env.clone())?;
+        admin.require_auth();
+
+        let first = Self::get_package(env.clone(), ids.first().ok_or(Error::InvalidState)?)?;
+        let token = first.token;
+        let mut total_amount: i128 = 0;
+        let mut expires_at: u64 = 0;
+        let mut never_expires = false;
+        let mut start_at: u64 = 0;
+        let mut metadata: Map<Symbol, String> = Map::new(&env);
+
+        for id in ids.iter() {
+            let source = Self::get_package(env.clone(), id)?;
+            if source.status != PackageStatus::Created || source.recipient != recipient {
+                return Err(Error::InvalidState);
+            }
+            if source.token != token {
+                return Err(Error::TokenMismatch);
+            }
+
+            total_amount += source.amount;
+            never_expires |= source.expires_at == 0;
+            expires_at = expires_at.max(source.expires_at);
+            start_at = start_at.max(source.start_at);
+            for (key, value) in source.metadata.iter() {
+                metadata.set(key, value);
+            }
+
+            // Free the source amount so the merged package can lock it again
+            Self::retire_package(&env, &admin, id)?;
+        }
+        if never_expires {
+            expires_at = 0;
+        }
+
+        Self::create_package_internal(
+            &env,
+            &admin,
+            new_id,
+            recipient,
+            total_amount,
+            token,
+            expires_at,
+            start_at,
+            metadata,
+            String::from_str(&env, ""),
+        )?;
+
+        Self::log_admin_action(&env, symbol_short!("merge"), Some(new_id), &admin, "");
+
+        PackagesMergedEvent {
+            source_ids: ids,
+            new_id,
+            total_amount,
+        }
+        .publish(&env);
+
+        Ok(new_id)
+    }
+
+    /// Sets the expiry duration applied by `create_package_default_expiry`.
+    pub fn set_default_expiry_duration(env: Env, seconds: u64) -> Result<(), Error> {
+        let admin = Self::get_admin(env.clone())?;
+        admin.require_auth();
+
+        env.storage().instance().set(&KEY_DEFAULT_EXPIRY, &seconds);
+        Self::log_admin_action(&env, symbol_short!("def_exp"), None, &admin, "");
+        Ok(())
+    }
+
+    pub fn get_default_expiry_duration(env: Env) -> Option<u64> {
+        env.storage().instance().get(&KEY_DEFAULT_EXPIRY)
+    }
+
+    /// Shortest allowed gap between creation and `expires_at`. `0` disables the check.
+    pub fn set_min_expiry_duration(env: Env, seconds: u64) -> Result<(), Error> {
+        let admin = Self::get_admin(env.clone())?;
+        admin.require_auth();
+
+        env.storage().instance().set(&KEY_MIN_EXPIRY, &seconds);
+        Self::log_admin_action(&env, symbol_short!("min_exp"), None, &admin, "");
+        Ok(())
+    }
+
+    /// Longest allowed gap between creation and `expires_at`. `0` disables the check.
+    pub fn set_max_expiry_duration(env: Env, seconds: u64) -> Result<(), Error> {
+        let admin = Self::get_admin(env.clone())?;
+        admin.require_auth();
+
+        env.storage().instance().set(&KEY_MAX_EXPIRY, &seconds);
+        Self::log_admin_action(&env, symbol_short!("max_exp"), None, &admin, "");
+        Ok(())
+    }
+
+    /// Caps `create_package` calls at `max_creates` per window of `window_ledgers` ledgers.
+    /// Either value set to `0` disables the limit.
+    pub fn set_rate_limit(env: Env, window_ledgers: u32, max_creates: u32) -> Result<(), Error> {
+        let admin = Self::get_admin(env.clone())?;
+        admin.require_auth();
+
+        env.storage()
+            .instance()
+            .set(&KEY_RATE_LIMIT, &(window_ledgers, max_creates));
+        env.storage().instance().remove(&KEY_RATE_STATE);
+        Self::log_admin_action(&env, symbol_short!("rate_lim"), None, &admin, "");
+        Ok(())
+    }
+
+    /// `(window_ledgers, max_creates, creates in the current window)`.
+    pub fn get_rate_limit_state(env: Env) -> (u32, u32, u32) {
+        let (window_ledgers, max_creates): (u32, u32) = env
+            .storage()
+            .instance()
+            .get(&KEY_RATE_LIMIT)
+            .unwrap_or((0, 0));
+        let (window_start, count): (u32, u32) = env
+            .storage()
+            .instance()
+            .get(&KEY_RATE_STATE)
+            .unwrap_or((0, 0));
+        let current = if env.ledger().sequence() >= window_start.saturating_add(window_ledgers) {
+            0
+        } else {
+            count
+        };
+        (window_ledgers, max_creates, current)
+    }
+
+    /// Returns `(min, max)` expiry durations; `0` means no bound.
+    pub fn get_expiry_bounds(env: Env) -> (u64, u64) {
+        let storage = env.storage().instance();
+        (
+            storage.get(&KEY_MIN_EXPIRY).unwrap_or(0),
+            storage.get(&KEY_MAX_EXPIRY).unwrap_or(0),
+        )
+    }
+
+    /// Same as `create_package`, with `expires_at = now + default expiry duration`.
+    /// (Named to fit Soroban's 32-character function name limit.)
+    pub fn create_package_default_expiry(
+        env: Env,
+        caller: Address,
+        id: u64,
+        recipient: Address,
+        amount: i128,
+        token: Address,
+    ) -> Result<u64, Error> {
+        let duration =
+            Self::get_default_expiry_duration(env.clone()).ok_or(Error::NoDefaultExpiry)?;
+        let expires_at = env.ledger().timestamp() + duration;
+        Self::create_package(
+            env, caller, id, recipient, amount, token, expires_at, 0, None, None,
+        )
+    }
+
+    /// Sets how long after `expires_at` recipients may still claim (`0` = hard cutoff).
+    pub fn set_claim_grace_period(env: Env, seconds: u64) -> Result<(), Error> {
+        let admin = Self::get_admin(env.clone())?;
+        admin.require_auth();
+
+        env.storage().instance().set(&KEY_GRACE_PERIOD, &seconds);
+        Self::log_admin_action(&env, symbol_short!("grace"), None, &admin, "");
+        Ok(())
+    }
+
+    pub fn get_claim_grace_period(env: Env) -> u64 {
+        env.storage().instance().get(&KEY_GRACE_PERIOD).unwrap_or(0)
+    }
+
+    /// Program-wide deadline (e.g. fiscal year end). Claims on packages that have their own
+    /// expiry are cut off at whichever comes first; packages that never expire are unaffected.
+    pub fn set_global_expiry_override(env: Env, expires_at: u64) -> Result<(), Error> {
+        let admin = Self::get_admin(env.clone())?;
+        admin.require_auth();
+
+        if expires_at <= env.ledger().timestamp() {
+            return Err(Error::InvalidExpiry);
+        }
+        env.storage()
+            .instance()
+            .set(&KEY_GLOBAL_EXPIRY, &expires_at);
+        Self::log_admin_action(&env, symbol_short!("glob_exp"), None, &admin, "");
+        Ok(())
+    }
+
+    pub fn clear_global_expiry(env: Env) -> Result<(), Error> {
+        let admin = Self::get_admin(env.clone())?;
+        admin.require_auth();
+
+        env.storage().instance().remove(&KEY_GLOBAL_EXPIRY);
+        Self::log_admin_action(&env, symbol_short!("glob_clr"), None, &admin, "");
+        Ok(())
+    }
+
+    /// `0` when no override is set.
+    pub fn get_global_expiry(env: Env) -> u64 {
+        env.storage()
+            .instance()
+            .get(&KEY_GLOBAL_EXPIRY)
+            .unwrap_or(0)
+    }
+
+    /// Expiry claims are checked against: `min(expires_at, global override)`, or `0` if the
+    /// package never expires.
+    pub fn get_effective_expiry(env: Env, id: u64) -> Result<u64, Error> {
+        let package = Self::get_package(env.clone(), id)?;
+        Ok(Self::effective_expiry(&env, &package))
+    }
+
+    /// Last timestamp at which the package can be claimed, including the grace period.
+    /// Returns `0` for packages that never expire.
+    pub fn get_claim_deadline(env: Env, id: u64) -> Result<u64, Error> {
+        let package = Self::get_package(env.clone(), id)?;
+        if package.expires_at == 0 {
+            return Ok(0);
+        }
+        Ok(Self::claim_deadline(&env, &package))
+    }
+
+    /// Sends future refunds to `destination` (e.g. a cold treasury) instead of the admin.
+    pub fn set_refund_destination(env: Env, destination: Address) -> Result<(), Error> {
+        let admin = Self::get_admin(env.clone())?;
+        admin.require_auth();
+
+        env.storage().instance().set(&KEY_REFUND_DEST, &destination);
+        Self::log_admin_action(&env, symbol_short!("refund_to"), None, &admin, "");
+        Ok(())
+    }
+
+    /// Configured refund destination; `None` means refunds go to the admin.
+    pub fn get_refund_destination(env: Env) -> Option<Address> {
+        env.storage().instance().get(&KEY_REFUND_DEST)
+    }
+
+    // --- Templates ---
+
+    /// Saves (or overwrites) a package template.
+    pub fn save_template(
+        env: Env,
+        template_id: u64,
+        token: Address,
+        amount: i128,
+        expiry_duration: u64,
+        metadata: Map<Symbol, String>,
+    ) -> Result<(), Error> {
+        let admin = Self::get_admin(env.clone())?;
+        admin.require_auth();
+
+        if amount <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+
+        let template = PackageTemplate {
+            token,
+            amount,
+            expiry_duration,
+            metadata,
+        };
+        env.storage()
+            .persistent()
+            .set(&(symbol_short!("tmpl"), template_id), &template);
+        Self::log_admin_action(&env, symbol_short!("save_tmpl"), None, &admin, "");
+        Ok(())
+    }
+
+    pub fn delete_template(env: Env, template_id: u64) -> Result<(), Error> {
+        let admin = Self::get_admin(env.clone())?;
+        admin.require_auth();
+
+        let key = (symbol_short!("tmpl"), template_id);
+        if !env.storage().persistent().has(&key) {
+            return Err(Error::TemplateNotFound);
+        }
+        env.storage().persistent().remove(&key);
+        Self::log_admin_action(&env, symbol_short!("del_tmpl"), None, &admin, "");
+        Ok(())
+    }
+
+    pub fn get_template(env: Env, template_id: u64) -> Result<PackageTemplate, Error> {
+        env.storage()
+            .persistent()
+            .get(&(symbol_short!("tmpl"), template_id))
+            .ok_or(Error::TemplateNotFound)
+    }
+
+    /// Creates `new_id` for `recipient` from a saved template,
+    /// with `expires_at = now + template.expiry_duration`.
+    pub fn create_package_from_template(
+        env: Env,
+        template_id: u64,
+        new_id: u64,
+        recipient: Address,
+    ) -> Result<u64, Error> {
+        Self::require_not_paused(&env)?;
+        let admin = Self::get_admin(env.clone())?;
+        admin.require_auth();
+
+        let template = Self::get_template(env.clone(), template_id)?;
+        let expires_at = if template.expiry_duration == 0 {
+            0
+        } else {
+            env.ledger().timestamp() + template.expiry_duration
+        };
+
+        Self::create_package_internal(
+            &env,
+            &admin,
+            new_id,
+            recipient,
+            template.amount,
+            template.token,
+            expires_at,
+            0,
+            template.metadata,
+            String::from_str(&env, ""),
+        )?;
+
+        Self::log_admin_action(&env, symbol_short!("from_tmpl"), Some(new_id), &admin, "");
+        Ok(new_id)
+    }
+
+    // --- Vesting ---
+
+    /// Creates a package whose `total_amount` is locked up front but released linearly
+    /// between `start_at` and `end_at`. Nothing can be claimed before `cliff_at` (`0` = no cliff).
+    pub fn create_vesting_package(
+        env: Env,
+        id: u64,
+        recipient: Address,
+        total_amount: i128,
+        token: Address,
+        start_at: u64,
+        end_at: u64,
+        cliff_at: u64,
+    ) -> Result<u64, Error> {
+        Self::require_not_paused(&env)?;
+        let admin = Self::get_admin(env.clone())?;
+        admin.require_auth();
+
+        if end_at <= start_at || (cliff_at != 0 && (cliff_at < start_at || cliff_at > end_at)) {
+            return Err(Error::InvalidSchedule);
+        }
+
+        Self::create_package_internal(
+            &env,
+            &admin,
+            id,
+            recipient,
+            total_amount,
+            token,
+            0,
+            cliff_at.max(start_at),
+            Map::new(&env),
+            String::from_str(&env, ""),
+        )?;
+
+        let schedule = VestingSchedule {
+            start_at,
+            end_at,
+            cliff_at,
+            total_amount,
+            claimed_amount: 0,
+        };
+        env.storage()
+            .persistent()
+            .set(&(symbol_short!("vest"), id), &schedule);
+
+        Self::log_admin_action(&env, symbol_short!("vesting"), Some(id), &admin, "");
+        Ok(id)
+    }
+
+    pub fn get_vesting_schedule(env: Env, id: u64) -> Option<VestingSchedule> {
+        env.storage().persistent().get(&(symbol_short!("vest"), id))
+    }
+
+    /// Amount vested so far (claimed or not). `0` for packages without a schedule.
+    pub fn get_vested_amount(env: Env, id: u64) -> i128 {
+        match Self::get_vesting_schedule(env.clone(), id) {
+            Some(schedule) => Self::vested_amount(&env, &schedule),
+            None => 0,
+        }
+    }
+
+    /// Recipient withdraws everything that has vested but not yet been claimed.
+    /// Returns the amount released.
+    pub fn claim_vested(env: Env, id: u64) -> Result<i128, Error> {
+        Self::require_not_paused(&env)?;
+        Self::get_package(env.clone(), id)?;
+        let schedule = Self::get_vesting_schedule(env.clone(), id).ok_or(Error::InvalidState)?;
+        let amount = Self::vested_amount(&env, &schedule) - schedule.claimed_amount;
+        Self::claim_internal(&env, id, amount, None, String::from_str(&env, ""))?;
+        Ok(amount)
+    }
+
+    // --- Milestones ---
+
+    /// Creates a package funded by the sum of `milestones` (amount, description).
+    /// Each stage must be released by the admin before the recipient can claim it.
+    pub fn create_package_with_milestones(
+        env: Env,
+        id: u64,
+        recipient: Address,
+        token: Address,
+        milestones: Vec<(i128, String)>,
+        expires_at: u64,
+    ) -> Result<u64, Error> {
+        Self::require_not_paused(&env)?;
+        let admin = Self::get_admin(env.clone())?;
+        admin.require_auth();
+
+        if milestones.is_empty() {
+            return Err(Error::InvalidSchedule);
+        }
+
+        let mut stages: Vec<Milestone> = Vec::new(&env);
+        let mut total: i128 = 0;
+        for (amount, description) in milestones.iter() {
+            if amount <= 0 {
+                return Err(Error::InvalidAmount);
+            }
+            stages.push_back(Milestone {
+                id: stages.len(),
+                amount,
+                released: false,
+                claimed: false,
+                description,
+            });
+            total += amount;
+        }
+
+        Self::create_package_internal(
+            &env,
+            &admin,
+            id,
+            recipient,
+            total,
+            token,
+            expires_at,
+            0,
+            Map::new(&env),
+            String::from_str(&env, ""),
+        )?;
+        env.storage()
+            .persistent()
+            .set(&(symbol_short!("ms"), id), &stages);
+
+        Self::log_admin_action(&env, symbol_short!("milestone"), Some(id), &admin, "");
+        Ok(id)
+    }
+
+    /// Admin approves a milestone so the recipient can claim it.
+    pub fn release_milestone(env: Env, package_id: u64, milestone_id: u32) -> Result<(), Error> {
+        Self::require_not_paused(&env)?;
+        let admin = Self::get_admin(env.clone())?;
+        admin.require_auth();
+
+        let package = Self::get_package(env.clone(), package_id)?;
+        if !package.status.is_active() {
+            return Err(Error::InvalidState);
+        }
+
+        let mut stages = Self::get_milestones(env.clone(), package_id);
+        let mut stage = stages.get(milestone_id).ok_or(Error::MilestoneNotFound)?;
+        if stage.released {
+            return Err(Error::InvalidState);
+        }
+        stage.released = true;
+        stages.set(milestone_id, stage);
+        env.storage()
+            .persistent()
+            .set(&(symbol_short!("ms"), package_id), &stages);
+
+        Self::log_admin_action(&env, symbol_short!("ms_rel"), Some(package_id), &admin, "");
+
+        MilestoneReleasedEvent {
+            package_id,
+            milestone_id,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Recipient withdraws a released milestone. The package becomes `Claimed`
+    /// once every milestone has been claimed.
+    pub fn claim_milestone(env: Env, package_id: u64, milestone_id: u32) -> Result<(), Error> {
+        Self::require_not_paused(&env)?;
+        Self::get_package(env.clone(), package_id)?;
+
+        let mut stages = Self::get_milestones(env.clone(), package_id);
+        let mut stage = stages.get(milestone_id).ok_or(Error::MilestoneNotFound)?;
+        if !stage.released || stage.claimed {
+            return Err(Error::InvalidState);
+        }
+        stage.claimed = true;
+        let amount = stage.amount;
+        stages.set(milestone_id, stage);
+        env.storage()
+            .persistent()
+            .set(&(symbol_short!("ms"), package_id), &stages);
+
+        Self::claim_internal(&env, package_id, amount, None, String::from_str(&env, ""))
+    }
+
+    pub fn get_milestones(env: Env, package_id: u64) -> Vec<Milestone> {
+        env.storage()
+            .persistent()
+            .get(&(symbol_short!("ms"), package_id))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    // --- Recurring Packages ---
+
+    /// Makes an active package recurring: once fully claimed, a copy expiring
+    /// `interval_seconds` later is created from the pool, up to `max_recurrences` times.
+    pub fn set_recurring(
+        env: Env,
+        id: u64,
+        interval_seconds: u64,
+        max_recurrences: u32,
+    ) -> Result<(), Error> {
+        let admin = Self::get_admin(env.clone())?;
+        admin.require_auth();
+
+        let package = Self::get_package(env.clone(), id)?;
+        if !package.status.is_active() {
+            return Err(Error::InvalidState);
+        }
+        Self::write_recurring_config(&env, id, interval_seconds, max_recurrences)?;
+        Self::log_admin_action(&env, symbol_short!("recurring"), Some(id), &admin, "");
+        Ok(())
+    }
+
+    /// Upgrades an unclaimed `Created` package to recurring without revoking and recreating it.
+    /// A stricter `set_recurring`: an existing configuration is never overwritten.
+    pub fn convert_to_recurring(
+        env: Env,
+        id: u64,
+        interval_seconds: u64,
+        max_recurrences: u32,
+    ) -> Result<(), Error> {
+        let package = Self::get_package(env.clone(), id)?;
+        if package.status != PackageStatus::Created {
+            return Err(Error::InvalidState);
+        }
+        if Self::get_recurring_config(env.clone(), id).is_some() {
+            return Err(Error::AlreadyRecurring);
+        }
+        Self::set_recurring(env, id, interval_seconds, max_recurrences)
+    }
+
+    pub fn get_recurring_config(env: Env, id: u64) -> Option<RecurringConfig> {
+        env.storage().persistent().get(&(symbol_short!("rec"), id))
+    }
+
+    /// Stops the cycle: claiming `id` will no longer create a follow-up package.
+    pub fn cancel_recurrence(env: Env, id: u64) -> Result<(), Error> {
+        let admin = Self::get_admin(env.clone())?;
+        admin.require_auth();
+
+        let key = (symbol_short!("rec"), id);
+        if !env.storage().persistent().has(&key) {
+            return Err(Error::InvalidState);
+        }
+        env.storage().persistent().remove(&key);
+        Self::log_admin_action(&env, symbol_short!("rec_cncl"), Some(id), &admin, "");
+        Ok(())
+    }
+
+    /// Counterpart of `convert_to_recurring`; same as `cancel_recurrence`.
+    pub fn remove_recurrence(env: Env, id: u64) -> Result<(), Error> {
+        Self::cancel_recurrence(env, id)
+    }
+
+    // --- Recipient Actions ---
+
+    /// Recipient claims everything left in the package.
+    pub fn claim(env: Env, id: u64) -> Result<(), Error> {
+        let memo = String::from_str(&env, "");
+        Self::claim_with_memo(env, id, memo)
+    }
+
+    /// `claim` with a recipient note (at most 256 bytes) recorded in the package history
+    /// and the `ClaimedEvent`.
+    pub fn claim_with_memo(env: Env, id: u64, memo: String) -> Result<(), Error> {
+        Self::require_not_paused(&env)?;
+        if memo.len() > MAX_MEMO_LEN {
+            return Err(Error::MemoTooLong);
+        }
+        let package = Self::get_package(env.clone(), id)?;
+        Self::claim_internal(&env, id, package.remaining_amount, None, memo)
+    }
+
+    /// Recipient withdraws `amount` from the package.
+    /// The package moves to `PartiallyClaimed` until `remaining_amount` reaches zero,
+    /// at which point it becomes `Claimed`.
+    pub fn partial_claim(env: Env, id: u64, amount: i128) -> Result<(), Error> {
+        Self::require_not_paused(&env)?;
+        Self::claim_internal(&env, id, amount, None, String::from_str(&env, ""))
+    }
+
+    /// Registered delegate claims everything left in the package.
+    /// Funds still go to `package.recipient`.
+    pub fn claim_as_delegate(env: Env, id: u64) -> Result<(), Error> {
+        Self::require_not_paused(&env)?;
+        let package = Self::get_package(env.clone(), id)?;
+        let delegate = Self::get_claim_delegate(env.clone(), id).ok_or(Error::NotAuthorized)?;
+        Self::claim_internal(
+            &env,
+            id,
+            package.remaining_amount,
+            Some(delegate),
+            String::from_str(&env, ""),
+        )
+    }
+
+    /// `claimer` claims everything left in the package with the recipient co-signing the call,
+    /// so no prior `delegate_claim` is needed. Funds still go to `package.recipient`.
+    pub fn claim_on_behalf(env: Env, id: u64, claimer: Address) -> Result<(), Error> {
+        Self::require_not_paused(&env)?;
+        let package = Self::get_package(env.clone(), id)?;
+        // `claim_internal` takes the claimer's signature
+        if claimer != package.recipient {
+            package.recipient.require_auth();
+        }
+        Self::claim_internal(
+            &env,
+            id,
+            package.remaining_amount,
+            Some(claimer),
+            String::from_str(&env, ""),
+        )
+    }
+
+    /// Recipient authorizes `delegate` to claim on their behalf. Replaces any existing delegate.
+    pub fn delegate_claim(env: Env, id: u64, delegate: Address) -> Result<(), Error> {
+        let package = Self::get_package(env.clone(), id)?;
+        package.recipient.require_auth();
+
+        if !package.status.is_active() {
+            return Err(Error::PackageNotActive);
+        }
+
+        env.storage()
+            .persistent()
+            .set(&(symbol_short!("del"), id), &delegate);
+        Ok(())
+    }
+
+    /// Recipient removes their claim delegate.
+    pub fn revoke_claim_delegate(env: Env, id: u64) -> Result<(), Error> {
+        let package = Self::get_package(env.clone(), id)?;
+        package.recipient.require_auth();
+
+        env.storage()
+            .persistent()
+            .remove(&(symbol_short!("del"), id));
+        Ok(())
+    }
+
+    /// True if the recipient could claim right now: the package is active, the recipient is
+    /// not blacklisted, the cliff has passed, the claim deadline (including grace) has not,
+    /// and any oracle condition holds.
+    pub fn is_package_claimable(env: Env, id: u64) -> Result<bool, Error> {
+        let package = Self::get_package(env.clone(), id)?;
+        let now = env.ledger().timestamp();
+
+        if !package.status.is_active()
+            || Self::is_blacklisted(env.clone(), package.recipient.clone())
+            || Self::check_kyc(&env, &package).is_err()
+            || now < package.start_at
+            || (package.expires_at > 0 && now > Self::claim_deadline(&env, &package))
+        {
+            return Ok(false);
+        }
+        Self::check_condition(env, id)
+    }
+
+    /// Net amount `claim` would pay out right now, after the token's fee.
+    /// Runs the same checks as `claim` and fails with the same errors, but changes nothing.
+    pub fn try_claim_preview(env: Env, id: u64) -> Result<i128, Error> {
+        Self::require_not_paused(&env)?;
+        let package = Self::get_package(env.clone(), id)?;
+        let now = env.ledger().timestamp();
+
+        if !package.status.is_active() {
+            return Err(Error::PackageNotActive);
+        }
+        if Self::is_blacklisted(env.clone(), package.recipient.clone()) {
+            return Err(Error::RecipientBlacklisted);
+        }
+        Self::check_kyc(&env, &package)?;
+        if now < package.start_at {
+            return Err(Error::PackageNotYetClaimable);
+        }
+        if package.expires_at > 0 && now > Self::claim_deadline(&env, &package) {
+            return Err(Error::PackageExpired);
+        }
+        if !Self::check_condition(env.clone(), id)? {
+            return Err(Error::ConditionNotMet);
+        }
+
+        let amount = package.remaining_amount;
+        Ok(amount - Self::fee_for(&env, &package.token, amount))
+    }
+
+    /// Like `try_claim_preview`, but failures are reported inside the result instead of
+    /// as an error, so wallets always get the full breakdown. An unknown ID yields zeros.
+    pub fn simulate_claim(env: Env, id: u64) -> Result<ClaimSimulation, Error> {
+        let failure_reason = Self::try_claim_preview(env.clone(), id)
+            .err()
+            .map(|err| err as u32);
+        let (gross_amount, fee_rate_bps, fee_amount) = match Self::get_package(env.clone(), id) {
+            Ok(package) => (
+                package.remaining_amount,
+                Self::get_fee_rate_bps(env.clone(), package.token.clone()),
+                Self::fee_for(&env, &package.token, package.remaining_amount),
+            ),
+            Err(_) => (0, 0, 0),
+        };
+
+        Ok(ClaimSimulation {
+            gross_amount,
+            fee_amount,
+            net_amount: gross_amount - fee_amount,
+            fee_rate_bps,
+            is_claimable: failure_reason.is_none(),
+            failure_reason,
+        })
+    }
+
+    pub fn get_package_condition(env: Env, id: u64) -> Option<OracleCondition> {
+        env.storage().persistent().get(&(symbol_short!("cond"), id))
+    }
+
+    /// Queries the package's oracle and evaluates its condition.
+    /// Packages without a condition always pass.
+    pub fn check_condition(env: Env, id: u64) -> Result<bool, Error> {
+        Self::get_package(env.clone(), id)?;
+        let Some(condition) = Self::get_package_condition(env.clone(), id) else {
+            return Ok(true);
+        };
+
+        let value = OracleClient::new(&env, &condition.oracle).get_value(&condition.data_key);
+        Ok(match condition.comparison {
+            ComparisonOp::GreaterThan => value > condition.threshold,
+            ComparisonOp::LessThan => value < condition.threshold,
+            ComparisonOp::Equals => value == condition.threshold,
+        })
+    }
+
+    pub fn get_claim_delegate(env: Env, id: u64) -> Option<Address> {
+        env.storage().persistent().get(&(symbol_short!("del"), id))
+    }
+
+    // --- Admin Actions ---
+
+    /// Admin manually triggers disbursement (overrides recipient claim need, strictly checks status).
+    pub fn disburse(env: Env, id: u64) -> Result<(), Error> {
+        Self::require_not_paused(&env)?;
+        let admin = Self::get_admin(env.clone())?;
+        admin.require_auth();
+
+        let (package, amount, net) = Self::disburse_internal(&env, &admin, id, None, true)?;
+        Self::decrement_locked(&env, &package.token, net);
+
+        Self::log_admin_action(&env, symbol_short!("disburse"), Some(id), &admin, "");
+
+        DisbursedEvent {
+            id,
+            admin: admin.clone(),
+            amount,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Like `disburse`, but pays `target` (e.g. a logistics partner) instead of the recipient.
+    /// `package.recipient` is left unchanged for auditability.
+    pub fn disburse_to(env: Env, id: u64, target: Address) -> Result<(), Error> {
+        Self::require_not_paused(&env)?;
+        let admin = Self::require_single_admin(&env)?;
+        Self::disburse_to_internal(&env, admin, id, target)
+    }
+
+    /// Disburses every package in `ids`, continuing past individual failures.
+    /// Locked totals are updated once per token after all disbursements.
+    pub fn disburse_batch(env: Env, ids: Vec<u64>) -> Result<BatchResult, Error> {
+        Self::require_not_paused(&env)?;
+        let admin = Self::get_admin(env.clone())?;
+        admin.require_auth();
+
+        let mut result = BatchResult {
+            successes: Vec::new(&env),
+            failures: Vec::new(&env),
+        };
+        let mut unlocked: Map<Address, i128> = Map::new(&env);
+
+        for id in ids.iter() {
+            match Self::disburse_internal(&env, &admin, id, None, true) {
+                Ok((package, amount, net)) => {
+                    let pending = unlocked.get(package.token.clone()).unwrap_or(0);
+                    unlocked.set(package.token.clone(), pending + net);
+
+                    Self::log_admin_action(&env, symbol_short!("disburse"), Some(id), &admin, "");
+                    DisbursedEvent {
+                        id,
+                        admin: admin.clone(),
+                        amount,
+                    }
+                    .publish(&env);
+                    result.successes.push_back(id);
+                }
+                Err(err) => result.failures.push_back((id, err as u32)),
+            }
+        }
+
+        for (token, amount) in unlocked.iter() {
+            Self::decrement_locked(&env, &token, amount);
+        }
+
+        Ok(result)
+    }
+
+    /// Admin revokes a package (Cancels it). Funds are effectively unlocked but remain in contract pool.
+    pub fn revoke(env: Env, id: u64) -> Result<(), Error> {
+        Self::require_not_paused(&env)?;
+        let admin = Self::require_single_admin(&env)?;
+        Self::revoke_and_unlock(&env, admin, id)
+    }
+
+    /// Recipient declines their own untouched package. Same effect as `revoke`:
+    /// the package becomes `Cancelled` and its funds are unlocked back to the pool.
+    pub fn cancel_package(env: Env, id: u64) -> Result<(), Error> {
+        Self::require_not_paused(&env)?;
+        let package = Self::get_package(env.clone(), id)?;
+        package.recipient.require_auth();
+
+        if package.status != PackageStatus::Created {
+            return Err(Error::PackageNotActive);
+        }
+
+        let package = Self::revoke_internal(&env, &package.recipient, id)?;
+        Self::decrement_locked(&env, &package.token, package.remaining_amount);
+
+        RecipientCancelledEvent {
+            id,
+            recipient: package.recipient,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Moves an active package to another escrow (e.g. when a program is consolidated).
+    /// The package ends here as `Transferred` (never refundable) and its funds are unlocked
+    /// back to this pool; the target re-creates it from its own pool, so both admins must
+    /// authorize the call.
+    pub fn transfer_package_to_contract(
+        env: Env,
+        id: u64,
+        target_escrow: Address,
+    ) -> Result<(), Error> {
+        Self::require_not_paused(&env)?;
+        let admin = Self::require_single_admin(&env)?;
+
+        let package = Self::close_package(&env, &admin, id, PackageStatus::Transferred)?;
+        Self::decrement_locked(&env, &package.token, package.remaining_amount);
+        Self::log_admin_action(&env, symbol_short!("transfer"), Some(id), &admin, "");
+
+        PackageTransferredOutEvent {
+            id,
+            target_escrow: target_escrow.clone(),
+        }
+        .publish(&env);
+
+        EscrowReceiverClient::new(&env, &target_escrow).receive_transferred_package(
+            &id,
+            &package.recipient,
+            &package.remaining_amount,
+            &package.token,
+            &package.expires_at,
+        );
+        Ok(())
+    }
+
+    /// `IEscrowReceiver` implementation: re-creates a package transferred from another escrow,
+    /// locking `amount` from this contract's pool. Keeps `id` unless auto-increment is enabled.
+    pub fn receive_transferred_package(
+        env: Env,
+        id: u64,
+        recipient: Address,
+        amount: i128,
+        token: Address,
+        expires_at: u64,
+    ) -> Result<u64, Error> {
+        Self::require_not_paused(&env)?;
+        let admin = Self::get_admin(env.clone())?;
+        admin.require_auth();
+
+        // The incoming ID belongs to the source escrow; with auto-increment on, this
+        // contract allocates its own
+        let requested = if Self::is_auto_increment_enabled(env.clone()) {
+            0
+        } else {
+            id
+        };
+        let id = Self::resolve_package_id(&env, requested)?;
+
+        Self::create_package_internal(
+            &env,
+            &admin,
+            id,
+            recipient,
+            amount,
+            token,
+            expires_at,
+            0,
+            Map::new(&env),
+            String::from_str(&env, ""),
+        )?;
+
+        Self::log_admin_action(&env, symbol_short!("receive"), Some(id), &admin, "");
+        Ok(id)
+    }
+
+    /// Revokes every package in `ids`, continuing past individual failures.
+    /// Locked totals are updated once per token after all revocations.
+    pub fn revoke_batch(env: Env, ids: Vec<u64>) -> Result<BatchResult, Error> {
+        Self::require_not_paused(&env)?;
+        let admin = Self::require_single_admin(&env)?;
+
+        let mut result = BatchResult {
+            successes: Vec::new(&env),
+            failures: Vec::new(&env),
+        };
+        let mut unlocked: Map<Address, i128> = Map::new(&env);
+
+        for id in ids.iter() {
+            match Self::revoke_internal(&env, &admin, id) {
+                Ok(package) => {
+                    let pending = unlocked.get(package.token.clone()).unwrap_or(0);
+                    unlocked.set(package.token.clone(), pending + package.remaining_amount);
+
+                    Self::log_admin_action(&env, symbol_short!("revoke"), Some(id), &admin, "");
+                    RevokedEvent {
+                        id,
+                        admin: admin.clone(),
+                        amount: package.remaining_amount,
+                    }
+                    .publish(&env);
+                    result.successes.push_back(id);
+                }
+                Err(err) => result.failures.push_back((id, err as u32)),
+            }
+        }
+
+        for (token, amount) in unlocked.iter() {
+            Self::decrement_locked(&env, &token, amount);
+        }
+
+        Ok(result)
+    }
+
+    /// Admin expires an active package whose deadline has passed, unlocking its remaining funds
+    /// without waiting for the recipient to touch it.
+    pub fn force_expire(env: Env, id: u64) -> Result<(), Error> {
+        Self::require_not_paused(&env)?;
+        let admin = Self::get_admin(env.clone())?;
+        admin.require_auth();
+
+        let package = Self::force_expire_internal(&env, &admin, id)?;
+        Self::decrement_locked(&env, &package.token, package.remaining_amount);
+
+        Self::log_admin_action(&env, symbol_short!("force_exp"), Some(id), &admin, "");
+
+        PackageExpiredEvent { id, admin }.publish(&env);
+
+        Ok(())
+    }
+
+    /// Force-expires every package in `ids`, continuing past individual failures.
+    /// Locked totals are updated once per token after all expirations.
+    pub fn force_expire_batch(env: Env, ids: Vec<u64>) -> Result<BatchResult, Error> {
+        Self::require_not_paused(&env)?;
+        let admin = Self::get_admin(env.clone())?;
+        admin.require_auth();
+
+        let mut result = BatchResult {
+            successes: Vec::new(&env),
+            failures: Vec::new(&env),
+        };
+        let mut unlocked: Map<Address, i128> = Map::new(&env);
+
+        for id in ids.iter() {
+            match Self::force_expire_internal(&env, &admin, id) {
+                Ok(package) => {
+                    let pending = unlocked.get(package.token.clone()).unwrap_or(0);
+                    unlocked.set(package.token.clone(), pending + package.remaining_amount);
+
+                    Self::log_admin_action(&env, symbol_short!("force_exp"), Some(id), &admin, "");
+                    PackageExpiredEvent {
+                        id,
+                        admin: admin.clone(),
+                    }
+                    .publish(&env);
+                    result.successes.push_back(id);
+                }
+                Err(err) => result.failures.push_back((id, err as u32)),
+            }
+        }
+
+        for (token, amount) in unlocked.iter() {
+            Self::decrement_locked(&env, &token, amount);
+        }
+
+        Ok(result)
+    }
+
+    pub fn refund(env: Env, id: u64) -> Result<(), Error> {
+        Self::require_not_paused(&env)?;
+        let admin = Self::require_single_admin(&env)?;
+        Self::refund_internal(&env, admin, id)
+    }
+
+    pub fn set_auto_refund_enabled(env: Env, enabled: bool) -> Result<(), Error> {
+        let admin = Self::get_admin(env.clone())?;
+        admin.require_auth();
+
+        env.storage().instance().set(&KEY_AUTO_REFUND, &enabled);
+        Self::log_admin_action(&env, symbol_short!("auto_ref"), None, &admin, "");
+        Ok(())
+    }
+
+    pub fn is_auto_refund_enabled(env: Env) -> bool {
+        env.storage()
+            .instance()
+            .get(&KEY_AUTO_REFUND)
+            .unwrap_or(false)
+    }
+
+    /// Runs `refund` for every `token` package in `ids` that is refundable and skips the rest.
+    /// Returns how many packages were refunded.
+    pub fn process_auto_refunds(env: Env, token: Address, ids: Vec<u64>) -> Result<u32, Error> {
+        Self::require_not_paused(&env)?;
+        if !Self::is_auto_refund_enabled(env.clone()) {
+            return Err(Error::AutoRefundDisabled);
+        }
+        let admin = Self::require_single_admin(&env)?;
+
+        let mut processed: u32 = 0;
+        for id in ids.iter() {
+            if !Self::get_package(env.clone(), id).is_ok_and(|p| p.token == token) {
+                continue;
+            }
+            if Self::refund_internal(&env, admin.clone(), id).is_ok() {
+                processed += 1;
+            }
+        }
+        Ok(processed)
+    }
+
+    /// Admin withdraws un-earmarked pool funds (e.g. overfunding) to themselves.
+    /// Locked package funds and refunds still owed on cancelled or expired packages
+    /// are never touched.
+    pub fn withdraw_excess(env: Env, token: Address, amount: i128) -> Result<(), Error> {
+        Self::require_not_paused(&env)?;
+        let admin = Self::require_single_admin(&env)?;
+        Self::withdraw_excess_internal(&env, admin, token, amount, None)
+    }
+
+    /// Same as `withdraw_excess`, but pays `destination` (e.g. a treasury) instead of the admin.
+    /// Only the admin authorizes; the destination does not need to sign.
+    pub fn withdraw_excess_to(
+        env: Env,
+        token: Address,
+        amount: i128,
+        destination: Address,
+    ) -> Result<(), Error> {
+        Self::require_not_paused(&env)?;
+        let admin = Self::require_single_admin(&env)?;
+        Self::withdraw_excess_internal(&env, admin, token, amount, Some(destination))
+    }
+
+    /// Recovery hatch: recomputes the locked total for `token` from `package_ids`
+    /// (the unclaimed amounts of its active packages) plus uncollected fees.
+    /// `package_ids` must cover every active package of `token`; duplicates are ignored.
+    pub fn rebalance_locked_map(
+        env: Env,
+        token: Address,
+        package_ids: Vec<u64>,
+    ) -> Result<i128, Error> {
+        let admin = Self::get_admin(env.clone())?;
+        admin.require_auth();
+
+        let mut seen: Map<u64, bool> = Map::new(&env);
+        let mut new_locked = Self::get_fees_accrued(env.clone(), token.clone());
+        for id in package_ids.iter() {
+            if seen.contains_key(id) {
+                continue;
+            }
+            seen.set(id, true);
+
+            let package = Self::get_package(env.clone(), id)?;
+            if package.token == token && package.status.is_active() {
+                new_locked += package.remaining_amount;
+            }
+        }
+
+        let old_locked = Self::get_total_locked(env.clone(), token.clone());
+        let mut locked_map: Map<Address, i128> = env
+            .storage()
+            .instance()
+            .get(&KEY_TOTAL_LOCKED)
+            .unwrap_or(Map::new(&env));
+        locked_map.set(token.clone(), new_locked);
+        env.storage().instance().set(&KEY_TOTAL_LOCKED, &locked_map);
+
+        Self::log_admin_action(&env, symbol_short!("rebalance"), None, &admin, "");
+
+        LockedMapRebalancedEvent {
+            token,
+            old_locked,
+            new_locked,
+        }
+        .publish(&env);
+
+        Ok(new_locked)
+    }
+
+    // --- Package Management ---
+
+    /// Admin moves the deadline of an active package.
+    /// `new_expires_at` must be `0` (no expiry) or in the future.
+    pub fn update_expiry(env: Env, id: u64, new_expires_at: u64) -> Result<(), Error> {
+        Self::require_not_paused(&env)?;
+        let admin = Self::get_admin(env.clone())?;
+        admin.require_auth();
+
+        let key = (symbol_short!("pkg"), id);
+        let mut package: Package = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .ok_or(Error::PackageNotFound)?;
+
+        if !package.status.is_active() {
+            return Err(Error::InvalidState);
+        }
+        if new_expires_at != 0 && new_expires_at <= env.ledger().timestamp() {
+            return Err(Error::InvalidExpiry);
+        }
+
+        let old_expires_at = package.expires_at;
+        package.expires_at = new_expires_at;
+        env.storage().persistent().set(&key, &package);
+        Self::update_expiry_index(&env, &package.token, id, old_expires_at, new_expires_at);
+
+        Self::log_admin_action(&env, symbol_short!("upd_exp"), Some(id), &admin, "");
+
+        PackageExpiryUpdatedEvent {
+            id,
+            old_expires_at,
+            new_expires_at,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Admin moves the deadline of every `Created` package in `ids` to `new_expires_at`,
+    /// silently skipping anything else. Returns the number of packages updated.
+    pub fn bulk_extend_expiry(env: Env, ids: Vec<u64>, new_expires_at: u64) -> Result<u32, Error> {
+        Self::require_not_paused(&env)?;
+        let admin = Self::get_admin(env.clone())?;
+        admin.require_auth();
+
+        if new_expires_at <= env.ledger().timestamp() {
+            return Err(Error::InvalidExpiry);
+        }
+        let (_, max) = Self::get_expiry_bounds(env.clone());
+        if max > 0 && new_expires_at - env.ledger().timestamp() > max {
+            return Err(Error::ExpiryTooLong);
+        }
+
+        let mut updated: u32 = 0;
+        for id in ids.iter() {
+            let key = (symbol_short!("pkg"), id);
+            let Some(mut package) = env.storage().persistent().get::<_, Package>(&key) else {
+                continue;
+            };
+            if package.status != PackageStatus::Created {
+                continue;
+            }
+
+            let old_expires_at = package.expires_at;
+            package.expires_at = new_expires_at;
+            env.storage().persistent().set(&key, &package);
+            Self::update_expiry_index(&env, &package.token, id, old_expires_at, new_expires_at);
+            Self::log_admin_action(&env, symbol_short!("upd_exp"), Some(id), &admin, "");
+
+            PackageExpiryUpdatedEvent {
+                id,
+                old_expires_at,
+                new_expires_at,
+            }
+            .publish(&env);
+            updated += 1;
+        }
+
+        Ok(updated)
+    }
+
+    /// Admin redirects an active package to a new beneficiary, keeping its ID, amount, and metadata.
+    pub fn reassign_recipient(env: Env, id: u64, new_recipient: Address) -> Result<(), Error> {
+        Self::require_not_paused(&env)?;
+        let admin = Self::get_admin(env.clone())?;
+        admin.require_auth();
+
+        let key = (symbol_short!("pkg"), id);
+        let mut package: Package = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .ok_or(Error::PackageNotFound)?;
+
+        if !package.status.is_active() {
+            return Err(Error::InvalidState);
+        }
+        if !Self::is_recipient_allowed(env.clone(), new_recipient.clone()) {
+            return Err(Error::RecipientNotAllowed);
+        }
+        if Self::is_blacklisted(env.clone(), new_recipient.clone()) {
+            return Err(Error::RecipientBlacklisted);
+        }
+        Self::check_recipient_package_limit(&env, &new_recipient)?;
+
+        let old_recipient = package.recipient.clone();
+        package.recipient = new_recipient.clone();
+        env.storage().persistent().set(&key, &package);
+
+        Self::adjust_recipient_locked(
+            &env,
+            &old_recipient,
+            &package.token,
+            -package.remaining_amount,
+        );
+        Self::adjust_recipient_locked(
+            &env,
+            &new_recipient,
+            &package.token,
+            package.remaining_amount,
+        );
+        Self::adjust_recipient_package_count(&env, &old_recipient, -1);
+        Self::adjust_recipient_package_count(&env, &new_recipient, 1);
+        Self::add_recipient_package(&env, &new_recipient, id);
+        Self::remove_from_recipient_index(&env, &old_recipient, id);
+        Self::add_to_recipient_index(&env, &new_recipient, id);
+
+        Self::log_admin_action(&env, symbol_short!("reassign"), Some(id), &admin, "");
+
+        RecipientReassignedEvent {
+            id,
+            old_recipient,
+            new_recipient,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Admin adds or overwrites a single metadata entry on an active package.
+    pub fn set_metadata_entry(env: Env, id: u64, key: Symbol, value: String) -> Result<(), Error> {
+        let admin = Self::get_admin(env.clone())?;
+        admin.require_auth();
+
+        let pkg_key = (symbol_short!("pkg"), id);
+        let mut package: Package = env
+            .storage()
+            .persistent()
+            .get(&pkg_key)
+            .ok_or(Error::PackageNotFound)?;
+
+        if !package.status.is_active() {
+            return Err(Error::InvalidState);
+        }
+        if Self::is_metadata_locked(env.clone(), id) {
+            return Err(Error::MetadataLocked);
+        }
+
+        package.metadata.set(key.clone(), value);
+        env.storage().persistent().set(&pkg_key, &package);
+
+        PackageMetadataUpdatedEvent { id, key }.publish(&env);
+
+        Self::log_admin_action(&env, symbol_short!("meta_set"), Some(id), &admin, "");
+        Ok(())
+    }
+
+    /// Admin deletes a single metadata entry from an active package.
+    pub fn remove_metadata_entry(env: Env, id: u64, key: Symbol) -> Result<(), Error> {
+        let admin = Self::get_admin(env.clone())?;
+        admin.require_auth();
+
+        let pkg_key = (symbol_short!("pkg"), id);
+        let mut package: Package = env
+            .storage()
+            .persistent()
+            .get(&pkg_key)
+            .ok_or(Error::PackageNotFound)?;
+
+        if !package.status.is_active() {
+            return Err(Error::InvalidState);
+        }
+        if Self::is_metadata_locked(env.clone(), id) {
+            return Err(Error::MetadataLocked);
+        }
+
+        package.metadata.remove(key.clone());
+        env.storage().persistent().set(&pkg_key, &package);
+
+        PackageMetadataUpdatedEvent { id, key }.publish(&env);
+
+        Self::log_admin_action(&env, symbol_short!("meta_rm"), Some(id), &admin, "");
+        Ok(())
+    }
+
+    pub fn get_metadata_entry(env: Env, id: u64, key: Symbol) -> Option<String> {
+        let package = Self::get_package(env, id).ok()?;
+        package.metadata.get(key)
+    }
+
+    /// Admin freezes a package's metadata for compliance. There is deliberately no unlock.
+    pub fn lock_metadata(env: Env, id: u64) -> Result<(), Error> {
+        let admin = Self::get_admin(env.clone())?;
+        admin.require_auth();
+        Self::get_package(env.clone(), id)?;
+
+        Self::store_index(&env, &(KEY_METADATA_LOCKED, id), &true);
+        Self::log_admin_action(&env, symbol_short!("meta_lock"), Some(id), &admin, "");
+        Ok(())
+    }
+
+    pub fn is_metadata_locked(env: Env, id: u64) -> bool {
+        env.storage()
+            .persistent()
+            .get(&(KEY_METADATA_LOCKED, id))
+            .unwrap_or(false)
+    }
+
+    /// Admin attaches a free-form field note to a package, replacing any previous one.
+    /// Allowed in any status, but not once the package's metadata is locked.
+    pub fn set_package_note(env: Env, id: u64, note: String) -> Result<(), Error> {
+        let admin = Self::get_admin(env.clone())?;
+        admin.require_auth();
+        Self::get_package(env.clone(), id)?;
+
+        if Self::is_metadata_locked(env.clone(), id) {
+            return Err(Error::MetadataLocked);
+        }
+
+        env.storage()
+            .persistent()
+            .set(&(symbol_short!("note"), id), &note);
+
+        PackageNoteSetEvent {
+            id,
+            note_hash: env.crypto().sha256(&note.to_bytes()).to_bytes(),
+        }
+        .publish(&env);
+
+        Self::log_admin_action(&env, symbol_short!("note"), Some(id), &admin, "");
+        Ok(())
+    }
+
+    pub fn get_package_note(env: Env, id: u64) -> Option<String> {
+        env.storage().persistent().get(&(symbol_short!("note"), id))
+    }
+
+    /// Admin tags an active package with a program category (food, medicine, ...).
+    /// Stored under `("cat", id)` rather than in `metadata`, whose values are strings
+    /// (a `Symbol` cannot be converted to a `String` on-chain). The category counts as
+    /// metadata for `lock_metadata`. Re-tagging moves the package between categories.
+    pub fn set_package_category(env: Env, id: u64, category: Symbol) -> Result<(), Error> {
+        let admin = Self::get_admin(env.clone())?;
+        admin.require_auth();
+
+        let package = Self::get_package(env.clone(), id)?;
+        if !package.status.is_active() {
+            return Err(Error::InvalidState);
+        }
+        if Self::is_metadata_locked(env.clone(), id) {
+            return Err(Error::MetadataLocked);
+        }
+
+        if let Some(previous) = Self::get_package_category(env.clone(), id) {
+            Self::remove_from_category_index(&env, &previous, id);
+        }
+        let mut ids = Self::category_bucket(&env, &category);
+        if ids.is_empty() {
+            let mut categories = Self::get_categories(env.clone());
+            categories.push_back(category.clone());
+            Self::store_index(&env, &KEY_CATEGORIES, &categories);
+        }
+        ids.push_back(id);
+        Self::store_index(&env, &(KEY_CATEGORY_INDEX, category.clone()), &ids);
+        env.storage()
+            .persistent()
+            .set(&(symbol_short!("cat"), id), &category);
+
+        PackageCategorySetEvent { id, category }.publish(&env);
+
+        Self::log_admin_action(&env, symbol_short!("category"), Some(id), &admin, "");
+        Ok(())
+    }
+
+    /// Admin sets the triage priority of an active package (`0` normal .. `255` critical).
+    pub fn set_package_priority(env: Env, id: u64, priority: u32) -> Result<(), Error> {
+        let admin = Self::get_admin(env.clone())?;
+        admin.require_auth();
+
+        if priority > MAX_PRIORITY {
+            return Err(Error::InvalidPriority);
+        }
+        let key = (symbol_short!("pkg"), id);
+        let mut package: Package = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .ok_or(Error::PackageNotFound)?;
+        if !package.status.is_active() {
+            return Err(Error::InvalidState);
+        }
+
+        Self::remove_from_priority_index(&env, package.priority, id);
+        Self::add_to_priority_index(&env, priority, id);
+        package.priority = priority;
+        env.storage().persistent().set(&key, &package);
+        Self::log_admin_action(&env, symbol_short!("priority"), Some(id), &admin, "");
+        Ok(())
+    }
+
+    /// Active packages with `priority >= min_priority`, highest priority first
+    /// (order of prioritization within a level). Every active package is indexed, so
+    /// `min_priority = 0` lists normal-priority packages after the prioritized ones.
+    pub fn get_packages_by_priority(
+        env: Env,
+        min_priority: u32,
+        offset: u32,
+        limit: u32,
+    ) -> Vec<u64> {
+        let mut ids = Vec::new(&env);
+        let mut skipped: u32 = 0;
+        for priority in Self::priority_levels(&env).iter().rev() {
+            if priority < min_priority {
+                break;
+            }
+            for id in Self::priority_bucket(&env, priority).iter() {
+                if ids.len() >= limit {
+                    return ids;
+                }
+                if skipped < offset {
+                    skipped += 1;
+                    continue;
+                }
+                ids.push_back(id);
+            }
+        }
+        ids
+    }
+
+    pub fn get_package_category(env: Env, id: u64) -> Option<Symbol> {
+        env.storage().persistent().get(&(symbol_short!("cat"), id))
+    }
+
+    /// Active packages in `category`, in the order they were tagged.
+    pub fn get_packages_by_category(
+        env: Env,
+        category: Symbol,
+        offset: u32,
+        limit: u32,
+    ) -> Vec<u64> {
+        let ids = Self::category_bucket(&env, &category);
+        let end = ids.len().min(offset.saturating_add(limit));
+        if offset >= end {
+            return Vec::new(&env);
+        }
+        ids.slice(offset..end)
+    }
+
+    /// Categories with at least one active package.
+    pub fn get_categories(env: Env) -> Vec<Symbol> {
+        env.storage()
+            .persistent()
+            .get(&KEY_CATEGORIES)
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Admin tops up an active package with `additional` funds from the available pool.
+    /// Vesting and milestone packages cannot be resized.
+    pub fn increase_package_amount(env: Env, id: u64, additional: i128) -> Result<(), Error> {
+        Self::require_not_paused(&env)?;
+        let admin = Self::get_admin(env.clone())?;
+        admin.require_auth();
+
+        if additional <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+
+        let key = (symbol_short!("pkg"), id);
+        let mut package: Package = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .ok_or(Error::PackageNotFound)?;
+
+        if !package.status.is_active() || Self::has_release_schedule(&env, id) {
+            return Err(Error::InvalidState);
+        }
+
+        let old_amount = package.amount;
+        let new_amount = old_amount + additional;
+        Self::check_amount_limits(&env, &package.token, new_amount)?;
+        Self::lock_funds(&env, &package.token, additional)?;
+
+        package.amount = new_amount;
+        package.remaining_amount += additional;
+        env.storage().persistent().set(&key, &package);
+        Self::adjust_recipient_locked(&env, &package.recipient, &package.token, additional);
+
+        Self::log_admin_action(&env, symbol_short!("increase"), Some(id), &admin, "");
+
+        PackageAmountIncreasedEvent {
+            id,
+            old_amount,
+            new_amount,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Admin cuts an active package by `reduction`, returning it to the pool.
+    /// The package cannot be reduced to zero; use `revoke` for that.
+    /// Vesting and milestone packages cannot be resized.
+    pub fn decrease_package_amount(env: Env, id: u64, reduction: i128) -> Result<(), Error> {
+        Self::require_not_paused(&env)?;
+        let admin = Self::get_admin(env.clone())?;
+        admin.require_auth();
+
+        let key = (symbol_short!("pkg"), id);
+        let mut package: Package = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .ok_or(Error::PackageNotFound)?;
+
+        if !package.status.is_active() || Self::has_release_schedule(&env, id) {
+            return Err(Error::InvalidState);
+        }
+        if reduction <= 0 || reduction >= package.remaining_amount {
+            return Err(Error::InvalidAmount);
+        }
+
+        let old_amount = package.amount;
+        package.amount -= reduction;
+        package.remaining_amount -= reduction;
+        env.storage().persistent().set(&key, &package);
+
+        Self::decrement_locked(&env, &package.token, reduction);
+        Self::adjust_recipient_locked(&env, &package.recipient, &package.token, -reduction);
+
+        Self::log_admin_action(&env, symbol_short!("decrease"), Some(id), &admin, "");
+
+        PackageAmountDecreasedEvent {
+            id,
+            old_amount,
+            new_amount: package.amount,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    // --- Storage TTL ---
+
+    /// Extends the on-ledger lifetime of a package so it does not get archived.
+    /// Anyone may pay to keep a package alive. `ledgers_to_extend` must be between `1`
+    /// and the network's maximum entry TTL.
+    pub fn extend_package_ttl(env: Env, id: u64, ledgers_to_extend: u32) -> Result<(), Error> {
+        let key = (symbol_short!("pkg"), id);
+        if !env.storage().persistent().has(&key) {
+            return Err(Error::PackageNotFound);
+        }
+        Self::check_ttl_extension(&env, ledgers_to_extend)?;
+        Self::extend_package_ttl_internal(&env, id, ledgers_to_extend);
+        Ok(())
+    }
+
+    /// Sets the TTL (in ledgers) applied to every newly created package. Must be between
+    /// `1` and the network's maximum entry TTL.
+    pub fn set_default_ttl_extension(env: Env, ledgers: u32) -> Result<(), Error> {
+        let admin = Self::get_admin(env.clone())?;
+        admin.require_auth();
+        Self::check_ttl_extension(&env, ledgers)?;
+
+        env.storage().instance().set(&KEY_DEFAULT_TTL, &ledgers);
+        Self::log_admin_action(&env, symbol_short!("def_ttl"), None, &admin, "");
+        Ok(())
+    }
+
+    /// Ledgers left before the package entry is archived, as recorded by the longest
+    /// extension made through this contract (`0` if it was never extended). This is a
+    /// lower bound: the entry may have been kept alive longer by other means.
+    pub fn get_package_ttl(env: Env, id: u64) -> u32 {
+        let live_until: u32 = env
+            .storage()
+            .persistent()
+            .get(&(symbol_short!("ttl"), id))
+            .unwrap_or(0);
+        live_until.saturating_sub(env.ledger().sequence())
+    }
+
+    /// Extends the lifetime of instance storage (admin, totals, and all config) by `ledgers`.
+    /// If the instance is archived the whole contract stops working, so this must be kept up.
+    pub fn extend_instance_ttl(env: Env, ledgers: u32) -> Result<(), Error> {
+        let admin = Self::get_admin(env.clone())?;
+        admin.require_auth();
+
+        if ledgers > MAX_INSTANCE_TTL_EXTENSION {
+            return Err(Error::InvalidTtlExtension);
+        }
+        Self::check_ttl_extension(&env, ledgers)?;
+
+        let live_until = (env.ledger().sequence() + ledgers)
+            .max(env.storage().instance().get(&KEY_INSTANCE_TTL).unwrap_or(0));
+        env.storage().instance().set(&KEY_INSTANCE_TTL, &live_until);
+        env.storage().instance().extend_ttl(ledgers, ledgers);
+        Self::log_admin_action(&env, symbol_short!("inst_ttl"), None, &admin, "");
+        Ok(())
+    }
+
+    /// Ledgers left before instance storage is archived, as recorded by the longest
+    /// `extend_instance_ttl` call (`0` if it was never called). This is a lower bound:
+    /// any other extension of the instance is not tracked.
+    pub fn get_instance_ttl(env: Env) -> u32 {
+        let live_until: u32 = env.storage().instance().get(&KEY_INSTANCE_TTL).unwrap_or(0);
+        live_until.saturating_sub(env.ledger().sequence())
+    }
+
+    // --- Helpers ---
+
+    /// Shared creation path: validates (including the expiry bounds and the creation rate
+    /// limit), locks funds, stores the package, and emits `PackageCreatedEvent`. Callers
+    /// handle pause and auth checks.
+    fn create_package_internal(
+        env: &Env,
+        creator: &Address,
+        id: u64,
+        recipient: Address,
+        amount: i128,
+        token: Address,
+        expires_at: u64,
+        start_at: u64,
+        metadata: Map<Symbol, String>,
+        external_ref: String,
+    ) -> Result<u64, Error> {
+        if amount <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+        if !Self::is_token_allowed(env.clone(), token.clone()) {
+            return Err(Error::TokenNotAllowed);
+        }
+        if !Self::is_recipient_allowed(env.clone(), recipient.clone()) {
+            return Err(Error::RecipientNotAllowed);
+        }
+        if Self::is_blacklisted(env.clone(), recipient.clone()) {
+            return Err(Error::RecipientBlacklisted);
+        }
+        Self::check_amount_limits(env, &token, amount)?;
+        Self::check_expiry_bounds(env, expires_at)?;
+        Self::consume_rate_limit(env)?;
+        Self::check_recipient_package_limit(env, &recipient)?;
+
+        // 1. Check ID Uniqueness
+        let key = (symbol_short!("pkg"), id);
+        if env.storage().persistent().has(&key) {
+            return Err(Error::PackageIdExists);
+        }
+        let ext_ref_key = (KEY_EXT_REF_INDEX, external_ref.clone());
+        if !external_ref.is_empty() && env.storage().persistent().has(&ext_ref_key) {
+            return Err(Error::ExternalRefExists);
+        }
+
+        // 2. Check Solvency & 3. Update Locked State
+        Self::lock_funds(env, &token, amount)?;
+
+        // 4. Create Package
+        let created_at = env.ledger().timestamp();
+        let package = Package {
+            id,
+            recipient: recipient.clone(),
+            amount,
+            remaining_amount: amount,
+            token: token.clone(),
+            status: PackageStatus::Created,
+            created_at,
+            expires_at,
+            start_at,
+            metadata,
+            external_ref: external_ref.clone(),
+            priority: 0,
+        };
+
+        env.storage().persistent().set(&key, &package);
+        Self::bump_counter(env, &KEY_PKG_COUNT, 1);
+        Self::record_transition(
+            env,
+            id,
+            PackageStatus::Created,
+            PackageStatus::Created,
+            creator,
+            String::from_str(env, ""),
+        );
+        Self::adjust_recipient_locked(env, &recipient, &token, amount);
+        Self::adjust_recipient_package_count(env, &recipient, 1);
+        Self::add_recipient_package(env, &recipient, id);
+        Self::add_to_recipient_index(env, &recipient, id);
+        Self::add_to_priority_index(env, 0, id);
+        Self::add_token_package(env, &token, id);
+        Self::push_id(env, &KEY_ALL_PKG_IDS, id);
+        Self::update_expiry_index(env, &token, id, 0, expires_at);
+        if !external_ref.is_empty() {
+            Self::store_index(env, &ext_ref_key, &id);
+        }
+
+        let default_ttl: Option<u32> = env.storage().instance().get(&KEY_DEFAULT_TTL);
+        if let Some(ledgers) = default_ttl {
+            Self::extend_package_ttl_internal(env, id, ledgers);
+        }
+
+        // Emit Event
+        PackageCreatedEvent {
+            id,
+            recipient,
+            amount,
+            created_at,
+            expires_at,
+        }
+        .publish(env);
+
+        Ok(id)
+    }
+
+    /// Rejects a TTL extension of `0` or beyond what the network allows for one entry.
+    fn check_ttl_extension(env: &Env, ledgers: u32) -> Result<(), Error> {
+        if ledgers == 0 || ledgers > env.storage().max_ttl() {
+            return Err(Error::InvalidTtlExtension);
+        }
+        Ok(())
+    }
+
+    fn extend_package_ttl_internal(env: &Env, id: u64, ledgers: u32) {
+        let key = (symbol_short!("pkg"), id);
+        let ttl_key = (symbol_short!("ttl"), id);
+        // `extend_ttl` never shortens an entry, so keep the furthest deadline on record
+        let live_until = (env.ledger().sequence() + ledgers)
+            .max(env.storage().persistent().get(&ttl_key).unwrap_or(0));
+
+        env.storage().persistent().set(&ttl_key, &live_until);
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, ledgers, ledgers);
+        env.storage()
+            .persistent()
+            .extend_ttl(&ttl_key, ledgers, ledgers);
+    }
+
+    /// Shared claim path for `claim`, `partial_claim`, `claim_as_delegate`, and `claim_on_behalf`.
+    /// `claimant` signs the claim; `None` means the recipient. Funds always go to the recipient.
+    fn claim_internal(
+        env: &Env,
+        id: u64,
+        amount: i128,
+        claimant: Option<Address>,
+        memo: String,
+    ) -> Result<(), Error> {
+        let key = (symbol_short!("pkg"), id);
+        let mut package: Package = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .ok_or(Error::PackageNotFound)?;
+
+        // Validations
+        if !package.status.is_active() {
+            return Err(Error::PackageNotActive);
+        }
+        if Self::is_blacklisted(env.clone(), package.recipient.clone()) {
+            return Err(Error::RecipientBlacklisted);
+        }
+        Self::check_kyc(env, &package)?;
+        // Check cliff
+        if env.ledger().timestamp() < package.start_at {
+            return Err(Error::PackageNotYetClaimable);
+        }
+        // Check expiry (claims are still accepted during the grace period)
+        if package.expires_at > 0 && env.ledger().timestamp() > Self::claim_deadline(env, &package)
+        {
+            // Auto-expire if accessed after date
+            Self::set_status(
+                env,
+                &mut package,
+                PackageStatus::Expired,
+                &env.current_contract_address(),
+            );
+            env.storage().persistent().set(&key, &package);
+            return Err(Error::PackageExpired);
+        }
+        if !Self::check_condition(env.clone(), id)? {
+            return Err(Error::ConditionNotMet);
+        }
+        if amount <= 0 || amount > package.remaining_amount {
+            return Err(Error::InvalidAmount);
+        }
+        // Vesting packages only release what has vested so far
+        let vest_key = (symbol_short!("vest"), id);
+        if let Some(mut schedule) = env
+            .storage()
+            .persistent()
+            .get::<_, VestingSchedule>(&vest_key)
+        {
+            if amount > Self::vested_amount(env, &schedule) - schedule.claimed_amount {
+                return Err(Error::InvalidAmount);
+            }
+            schedule.claimed_amount += amount;
+            env.storage().persistent().set(&vest_key, &schedule);
+        }
+        // Milestone packages only release stages marked claimed by `claim_milestone`
+        let stages = Self::get_milestones(env.clone(), id);
+        if !stages.is_empty() {
+            let unlocked: i128 = stages.iter().filter(|m| m.claimed).map(|m| m.amount).sum();
+            let paid = package.amount - package.remaining_amount;
+            if amount > unlocked - paid {
+                return Err(Error::InvalidAmount);
+            }
+        }
+
+        // Auth: the recipient, or whoever claims for them (delegate or co-signed claimer)
+        let claimant = claimant.unwrap_or(package.recipient.clone());
+        claimant.require_auth();
+
+        // State Transition: Created/PartiallyClaimed -> PartiallyClaimed/Claimed
+        // Checks passed, update state FIRST (Re-entrancy protection)
+        package.remaining_amount -= amount;
+        Self::adjust_recipient_locked(env, &package.recipient, &package.token, -amount);
+        let new_status = if package.remaining_amount == 0 {
+            PackageStatus::Claimed
+        } else {
+            PackageStatus::PartiallyClaimed
+        };
+        Self::set_status_with_memo(env, &mut package, new_status, &claimant, memo.clone());
+        env.storage().persistent().set(&key, &package);
+
+        // Update Global Locked (only the withdrawn portion) & Transfer Funds net of fees
+        Self::pay_out(env, &package.token, &package.recipient, amount);
+        Self::add_to_total(env, &KEY_TOTAL_DISBURSED, &package.token, amount);
+
+        // Emit Event
+        ClaimedEvent {
+            id,
+            recipient: package.recipient.clone(),
+            claimant,
+            amount,
+            memo,
+        }
+        .publish(env);
+
+        if package.status == PackageStatus::Claimed {
+            Self::recur(env, &package);
+        }
+
+        Ok(())
+    }
+
+    /// Re-creates a fully claimed recurring package under the next free ID.
+    /// Best effort: if the new package cannot be created (the pool cannot back it, or a
+    /// creation limit rejects it) the cycle simply ends, so the recipient's claim never
+    /// fails because of it.
+    fn recur(env: &Env, package: &Package) {
+        let Some(config) = Self::get_recurring_config(env.clone(), package.id) else {
+            return;
+        };
+        if config.recurrence_count >= config.max_recurrences {
+            return;
+        }
+
+        let new_id = Self::next_package_id(env);
+        let expires_at = env.ledger().timestamp() + config.interval_seconds;
+        let created = Self::create_package_internal(
+            env,
+            &package.recipient,
+            new_id,
+            package.recipient.clone(),
+            package.amount,
+            package.token.clone(),
+            expires_at,
+            0,
+            package.metadata.clone(),
+            String::from_str(env, ""),
+        );
+        if created.is_err() {
+            return;
+        }
+
+        let next = RecurringConfig {
+            recurrence_count: config.recurrence_count + 1,
+            ..config
+        };
+        env.storage()
+            .persistent()
+            .set(&(symbol_short!("rec"), new_id), &next);
+        env.storage().instance().set(&KEY_NEXT_ID, &(new_id + 1));
+
+        PackageRecurredEvent {
+            id: package.id,
+            new_id,
+        }
+        .publish(env);
+    }
+
+    fn write_recurring_config(
+        env: &Env,
+        id: u64,
+        interval_seconds: u64,
+        max_recurrences: u32,
+    ) -> Result<(), Error> {
+        if Self::has_release_schedule(env, id) {
+            return Err(Error::InvalidState);
+        }
+        if interval_seconds == 0 {
+            return Err(Error::InvalidSchedule);
+        }
+
+        let config = RecurringConfig {
+            interval_seconds,
+            max_recurrences,
+            recurrence_count: 0,
+        };
+        env.storage()
+            .persistent()
+            .set(&(symbol_short!("rec"), id), &config);
+        Ok(())
+    }
+
+    /// With auto-increment on, `requested` must be `0` and the next free ID is claimed;
+    /// otherwise `requested` is used as-is.
+    fn resolve_package_id(env: &Env, requested: u64) -> Result<u64, Error> {
+        if !Self::is_auto_increment_enabled(env.clone()) {
+            return Ok(requested);
+        }
+        if requested != 0 {
+            return Err(Error::AutoIncrementEnabled);
+        }
+        let id = Self::next_package_id(env);
+        env.storage().instance().set(&KEY_NEXT_ID, &(id + 1));
+        Ok(id)
+    }
+
+    /// Lowest unused package ID at or after the `KEY_NEXT_ID` cursor.
+    fn next_package_id(env: &Env) -> u64 {
+        let mut id: u64 = env.storage().instance().get(&KEY_NEXT_ID).unwrap_or(1);
+        while env.storage().persistent().has(&(symbol_short!("pkg"), id)) {
+            id += 1;
+        }
+        id
+    }
+
+    /// `total_amount * elapsed / duration`, clamped to `[0, total_amount]`; `0` before the cliff.
+    fn vested_amount(env: &Env, schedule: &VestingSchedule) -> i128 {
+        let now = env.ledger().timestamp();
+        if now < schedule.cliff_at || now <= schedule.start_at {
+            return 0;
+        }
+        if now >= schedule.end_at {
+            return schedule.total_amount;
+        }
+        let elapsed = (now - schedule.start_at) as i128;
+        let duration = (schedule.end_at - schedule.start_at) as i128;
+        schedule.total_amount * elapsed / duration
+    }
+
+    /// True for vesting and milestone packages, whose amounts are fixed by their schedule.
+    fn has_release_schedule(env: &Env, id: u64) -> bool {
+        env.storage().persistent().has(&(symbol_short!("vest"), id))
+            || env.storage().persistent().has(&(symbol_short!("ms"), id))
+    }
+
+    fn claim_deadline(env: &Env, package: &Package) -> u64 {
+        Self::effective_expiry(env, package)
+            .saturating_add(Self::get_claim_grace_period(env.clone()))
+    }
+
+    fn effective_expiry(env: &Env, package: &Package) -> u64 {
+        let global = Self::get_global_expiry(env.clone());
+        if global > 0 && package.expires_at > 0 {
+            package.expires_at.min(global)
+        } else {
+            package.expires_at
+        }
+    }
+
+    /// Moves `package` to `status`, keeping the per-status counters and the recipient
+    /// locked index in sync. Callers are responsible for persisting the package.
+    /// Every status change goes through here so the counters, indices, and history stay in sync.
+    fn set_status(env: &Env, package: &mut Package, status: PackageStatus, actor: &Address) {
+        Self::set_status_with_memo(env, package, status, actor, String::from_str(env, ""));
+    }
+
+    fn set_status_with_memo(
+        env: &Env,
+        package: &mut Package,
+        status: PackageStatus,
+        actor: &Address,
+        memo: String,
+    ) {
+        Self::record_transition(env, package.id, package.status, status, actor, memo);
+        let awaiting_refund =
+            |s: PackageStatus| matches!(s, PackageStatus::Cancelled | PackageStatus::Expired);
+        if package.status.is_active() && awaiting_refund(status) {
+            Self::add_to_total(
+                env,
+                &KEY_TOTAL_CANCELLED,
+                &package.token,
+                package.remaining_amount,
+            );
+        } else if awaiting_refund(package.status) && status == PackageStatus::Refunded {
+            Self::add_to_total(
+                env,
+                &KEY_TOTAL_CANCELLED,
+                &package.token,
+                -package.remaining_amount,
+            );
+        }
+        if package.status.is_active() && !status.is_active() {
+            Self::adjust_recipient_locked(
+                env,
+                &package.recipient,
+                &package.token,
+                -package.remaining_amount,
+            );
+            Self::adjust_recipient_package_count(env, &package.recipient, -1);
+            Self::remove_from_recipient_index(env, &package.recipient, package.id);
+            Self::update_expiry_index(env, &package.token, package.id, package.expires_at, 0);
+            if let Some(category) = Self::get_package_category(env.clone(), package.id) {
+                Self::remove_from_category_index(env, &category, package.id);
+            }
+            Self::remove_from_priority_index(env, package.priority, package.id);
+        }
+        if let Some(counter) = Self::status_counter(package.status) {
+            Self::bump_counter(env, &counter, -1);
+        }
+        if let Some(counter) = Self::status_counter(status) {
+            Self::bump_counter(env, &counter, 1);
+        }
+        package.status = status;
+    }
+
+    fn check_kyc(env: &Env, package: &Package) -> Result<(), Error> {
+        if Self::package_requires_kyc(env.clone(), package.id)
+            && !Self::is_kyc_attested(env.clone(), package.recipient.clone())
+        {
+            return Err(Error::KycRequired);
+        }
+        Ok(())
+    }
+
+    /// Rejects a new active package for `recipient` if it would exceed the configured cap.
+    fn check_recipient_package_limit(env: &Env, recipient: &Address) -> Result<(), Error> {
+        let max = Self::get_max_packages_per_recipient(env.clone());
+        if max > 0 && Self::get_recipient_active_pkg_count(env.clone(), recipient.clone()) >= max {
+            return Err(Error::RecipientPackageLimitExceeded);
+        }
+        Ok(())
+    }
+
+    fn add_recipient_package(env: &Env, recipient: &Address, id: u64) {
+        Self::push_id(env, &(KEY_RECIPIENT_PKGS, recipient.clone()), id);
+    }
+
+    /// Length of the append-only ID list `list`. Lists are stored as pages of
+    /// `ID_PAGE_SIZE` IDs under `(list, page)`, with the length under `list` itself,
+    /// so no single entry grows without bound.
+    fn id_list_len<K: IntoVal<Env, Val>>(env: &Env, list: &K) -> u32 {
+        env.storage().persistent().get(list).unwrap_or(0)
+    }
+
+    fn push_id<K>(env: &Env, list: &K, id: u64)
+    where
+        K: IntoVal<Env, Val> + Clone,
+        (K, u32): IntoVal<Env, Val>,
+    {
+        let len = Self::id_list_len(env, list);
+        let page_key = (list.clone(), len / ID_PAGE_SIZE);
+        let mut page: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&page_key)
+            .unwrap_or(Vec::new(env));
+        page.push_back(id);
+        Self::store_index(env, &page_key, &page);
+        Self::store_index(env, list, &(len + 1));
+    }
+
+    /// Up to `limit` IDs of `list` starting at `offset`, reading only the pages involved.
+    fn id_list_slice<K>(env: &Env, list: &K, offset: u32, limit: u32) -> Vec<u64>
+    where
+        K: IntoVal<Env, Val> + Clone,
+        (K, u32): IntoVal<Env, Val>,
+    {
+        let end = Self::id_list_len(env, list).min(offset.saturating_add(limit));
+        let mut ids = Vec::new(env);
+        let mut index = offset;
+        while index < end {
+            let page_no = index / ID_PAGE_SIZE;
+            let page: Vec<u64> = env
+                .storage()
+                .persistent()
+                .get(&(list.clone(), page_no))
+                .unwrap_or(Vec::new(env));
+            let page_end = end.min((page_no + 1) * ID_PAGE_SIZE);
+            for position in (index - page_no * ID_PAGE_SIZE)..(page_end - page_no * ID_PAGE_SIZE) {
+                if let Some(id) = page.get(position) {
+                    ids.push_back(id);
+                }
+            }
+            index = page_end;
+        }
+        ids
+    }
+
+    fn add_token_package(env: &Env, token: &Address, id: u64) {
+        Self::push_id(env, &(KEY_TOKEN_PKG_INDEX, token.clone()), id);
+    }
+
+    fn recipient_active_ids(env: &Env, recipient: &Address) -> Vec<u64> {
+        env.storage()
+            .persistent()
+            .get(&(KEY_RECIPIENT_INDEX, recipient.clone()))
+            .unwrap_or(Vec::new(env))
+    }
+
+    fn add_to_recipient_index(env: &Env, recipient: &Address, id: u64) {
+        let mut ids = Self::recipient_active_ids(env, recipient);
+        ids.push_back(id);
+        Self::store_index(env, &(KEY_RECIPIENT_INDEX, recipient.clone()), &ids);
+    }
+
+    fn remove_from_recipient_index(env: &Env, recipient: &Address, id: u64) {
+        let key = (KEY_RECIPIENT_INDEX, recipient.clone());
+        let mut ids = Self::recipient_active_ids(env, recipient);
+        let Some(pos) = ids.first_index_of(id) else {
+            return;
+        };
+        ids.remove(pos);
+        if ids.is_empty() {
+            env.storage().persistent().remove(&key);
+        } else {
+            Self::store_index(env, &key, &ids);
+        }
+    }
+
+    fn adjust_recipient_package_count(env: &Env, recipient: &Address, delta: i32) {
+        let key = (KEY_RECIPIENT_PKG_COUNT, recipient.clone());
+        let current: u32 = env.storage().persistent().get(&key).unwrap_or(0);
+        let updated = current.saturating_add_signed(delta);
+        if updated == 0 {
+            env.storage().persistent().remove(&key);
+        } else {
+            Self::store_index(env, &key, &updated);
+        }
+    }
+
+    /// Moves `id` from the bucket of `old_expires_at` to that of `new_expires_at` in
+    /// `token`'s expiry index. `0` on either side means "not indexed".
+    fn update_expiry_index(
+        env: &Env,
+        token: &Address,
+        id: u64,
+        old_expires_at: u64,
+        new_expires_at: u64,
+    ) {
+        if old_expires_at > 0 {
+            let bucket = old_expires_at / EXPIRY_BUCKET_SECONDS;
+            let mut entries = Self::expiry_bucket(env, token, bucket);
+            entries.remove(id);
+            let key = (symbol_short!("exp_idx"), token.clone(), bucket);
+            if entries.is_empty() {
+                env.storage().persistent().remove(&key);
+                let mut buckets = Self::expiry_buckets(env, token);
+                if let Some(pos) = buckets.first_index_of(bucket) {
+                    buckets.remove(pos);
+                }
+                Self::store_index(env, &(symbol_short!("exp_bkts"), token.clone()), &buckets);
+            } else {
+                Self::store_index(env, &key, &entries);
+            }
+        }
+        if new_expires_at > 0 {
+            let bucket = new_expires_at / EXPIRY_BUCKET_SECONDS;
+            let mut entries = Self::expiry_bucket(env, token, bucket);
+            if entries.is_empty() {
+                // Keep the bucket list sorted so queries can stop at the cutoff
+                let mut buckets = Self::expiry_buckets(env, token);
+                let pos = buckets
+                    .iter()
+                    .position(|b| b > bucket)
+                    .unwrap_or(buckets.len() as usize);
+                buckets.insert(pos as u32, bucket);
+                Self::store_index(env, &(symbol_short!("exp_bkts"), token.clone()), &buckets);
+            }
+            entries.set(id, new_expires_at);
+            Self::store_index(
+                env,
+                &(symbol_short!("exp_idx"), token.clone(), bucket),
+                &entries,
+            );
+        }
+    }
+
+    /// Writes a persistent index entry and keeps it from being archived while in use.
+    fn store_index<K, V>(env: &Env, key: &K, value: &V)
+    where
+        K: IntoVal<Env, Val>,
+        V: IntoVal<Env, Val>,
+    {
+        let storage = env.storage().persistent();
+        storage.set(key, value);
+        storage.extend_ttl(key, INDEX_TTL_THRESHOLD, INDEX_TTL_EXTEND_TO);
+    }
+
+    fn category_bucket(env: &Env, category: &Symbol) -> Vec<u64> {
+        env.storage()
+            .persistent()
+            .get(&(KEY_CATEGORY_INDEX, category.clone()))
+            .unwrap_or(Vec::new(env))
+    }
+
+    /// Drops `id` from `category`, removing the category entirely once it is empty.
+    fn remove_from_category_index(env: &Env, category: &Symbol, id: u64) {
+        let mut ids = Self::category_bucket(env, category);
+        let Some(pos) = ids.first_index_of(id) else {
+            return;
+        };
+        ids.remove(pos);
+        let key = (KEY_CATEGORY_INDEX, category.clone());
+        if ids.is_empty() {
+            env.storage().persistent().remove(&key);
+            let mut categories = Self::get_categories(env.clone());
+            if let Some(pos) = categories.first_index_of(category) {
+                categories.remove(pos);
+            }
+            Self::store_index(env, &KEY_CATEGORIES, &categories);
+        } else {
+            Self::store_index(env, &key, &ids);
+        }
+    }
+
+    fn record_transition(
+        env: &Env,
+        id: u64,
+        from_status: PackageStatus,
+        to_status: PackageStatus,
+        actor: &Address,
+        memo: String,
+    ) {
+        let key = (symbol_short!("hist"), id);
+        let mut history: Vec<StatusTransition> = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or(Vec::new(env));
+        history.push_back(StatusTransition {
+            from_status,
+            to_status,
+            timestamp: env.ledger().timestamp(),
+            actor: actor.clone(),
+            memo,
+        });
+        Self::store_index(env, &key, &history);
+    }
+
+    fn priority_levels(env: &Env) -> Vec<u32> {
+        env.storage()
+            .persistent()
+            .get(&KEY_PRIORITY_LEVELS)
+            .unwrap_or(Vec::new(env))
+    }
+
+    /// Active package IDs at one priority level, stored under `("prio", priority)`.
+    fn priority_bucket(env: &Env, priority: u32) -> Vec<u64> {
+        env.storage()
+            .persistent()
+            .get(&(symbol_short!("prio"), priority))
+            .unwrap_or(Vec::new(env))
+    }
+
+    fn add_to_priority_index(env: &Env, priority: u32, id: u64) {
+        let mut ids = Self::priority_bucket(env, priority);
+        if ids.is_empty() {
+            let mut levels = Self::priority_levels(env);
+            let pos = levels
+                .iter()
+                .position(|l| l > priority)
+                .unwrap_or(levels.len() as usize);
+            levels.insert(pos as u32, priority);
+            Self::store_index(env, &KEY_PRIORITY_LEVELS, &levels);
+        }
+        ids.push_back(id);
+        Self::store_index(env, &(symbol_short!("prio"), priority), &ids);
+    }
+
+    fn remove_from_priority_index(env: &Env, priority: u32, id: u64) {
+        let mut ids = Self::priority_bucket(env, priority);
+        let Some(pos) = ids.first_index_of(id) else {
+            return;
+        };
+        ids.remove(pos);
+        if ids.is_empty() {
+            env.storage()
+                .persistent()
+                .remove(&(symbol_short!("prio"), priority));
+            let mut levels = Self::priority_levels(env);
+            if let Some(pos) = levels.first_index_of(priority) {
+                levels.remove(pos);
+            }
+            Self::store_index(env, &KEY_PRIORITY_LEVELS, &levels);
+        } else {
+            Self::store_index(env, &(symbol_short!("prio"), priority), &ids);
+        }
+    }
+
+    /// Sorted buckets (`expires_at / EXPIRY_BUCKET_SECONDS`) holding `token`'s active packages.
+    fn expiry_buckets(env: &Env, token: &Address) -> Vec<u64> {
+        env.storage()
+            .persistent()
+            .get(&(symbol_short!("exp_bkts"), token.clone()))
+            .unwrap_or(Vec::new(env))
+    }
+
+    /// Active package id -> expires_at for one week of `token`'s expiries.
+    fn expiry_bucket(env: &Env, token: &Address, bucket: u64) -> Map<u64, u64> {
+        env.storage()
+            .persistent()
+            .get(&(symbol_short!("exp_idx"), token.clone(), bucket))
+            .unwrap_or(Map::new(env))
+    }
+
+    /// Applies `delta` to the `(recipient, token)` entry of the recipient locked index.
+    fn adjust_recipient_locked(env: &Env, recipient: &Address, token: &Address, delta: i128) {
+        let key = (KEY_RECIPIENT_LOCKED, recipient.clone(), token.clone());
+        let updated = env.storage().persistent().get(&key).unwrap_or(0) + delta;
+        if updated == 0 {
+            env.storage().persistent().remove(&key);
+        } else {
+            Self::store_index(env, &key, &updated);
+        }
+    }
+
+    /// Adds `amount` to the running per-token total stored under `key`.
+    fn add_to_total(env: &Env, key: &Symbol, token: &Address, amount: i128) {
+        let mut totals = Self::read_totals(env, key);
+        let current = totals.get(token.clone()).unwrap_or(0);
+        totals.set(token.clone(), current + amount);
+        env.storage().instance().set(key, &totals);
+    }
+
+    fn read_totals(env: &Env, key: &Symbol) -> Map<Address, i128> {
+        env.storage().instance().get(key).unwrap_or(Map::new(env))
+    }
+
+    fn status_counter(status: PackageStatus) -> Option<Symbol> {
+        match status {
+            PackageStatus::Claimed => Some(KEY_CNT_CLAIMED),
+            PackageStatus::Disbursed => Some(KEY_CNT_DISBURSED),
+            PackageStatus::Cancelled => Some(KEY_CNT_CANCELLED),
+            PackageStatus::Expired => Some(KEY_CNT_EXPIRED),
+            PackageStatus::Refunded => Some(KEY_CNT_REFUNDED),
+            PackageStatus::Transferred => Some(KEY_CNT_TRANSFERRED),
+            PackageStatus::Created | PackageStatus::PartiallyClaimed => None,
+        }
+    }
+
+    fn bump_counter(env: &Env, counter: &Symbol, delta: i64) {
+        let current: u64 = env.storage().persistent().get(counter).unwrap_or(0);
+        let updated = current.saturating_add_signed(delta);
+        Self::store_index(env, counter, &updated);
+    }
+
+    fn read_counter(env: &Env, counter: &Symbol) -> u64 {
+        env.storage().persistent().get(counter).unwrap_or(0)
+    }
+
+    /// Counts one creation against the current rate-limit window, starting a new window
+    /// once `window_ledgers` have passed.
+    fn consume_rate_limit(env: &Env) -> Result<(), Error> {
+        let (window_ledgers, max_creates, _) = Self::get_rate_limit_state(env.clone());
+        if window_ledgers == 0 || max_creates == 0 {
+            return Ok(());
+        }
+
+        let sequence = env.ledger().sequence();
+        let (mut window_start, mut count): (u32, u32) = env
+            .storage()
+            .instance()
+            .get(&KEY_RATE_STATE)
+            .unwrap_or((sequence, 0));
+        if sequence >= window_start.saturating_add(window_ledgers) {
+            window_start = sequence;
+            count = 0;
+        }
+        if count >= max_creates {
+            return Err(Error::RateLimitExceeded);
+        }
+
+        env.storage()
+            .instance()
+            .set(&KEY_RATE_STATE, &(window_start, count + 1));
+        Ok(())
+    }
+
+    /// Packages without an expiry (`expires_at == 0`) are exempt from the bounds.
+    fn check_expiry_bounds(env: &Env, expires_at: u64) -> Result<(), Error> {
+        if expires_at == 0 {
+            return Ok(());
+        }
+        let (min, max) = Self::get_expiry_bounds(env.clone());
+        let duration = expires_at.saturating_sub(env.ledger().timestamp());
+        if duration < min {
+            return Err(Error::ExpiryTooShort);
+        }
+        if max > 0 && duration > max {
+            return Err(Error::ExpiryTooLong);
+        }
+        Ok(())
+    }
+
+    /// Validates a package amount against the configured per-token bounds.
+    fn check_amount_limits(env: &Env, token: &Address, amount: i128) -> Result<(), Error> {
+        let (min, max) = Self::get_token_limits(env.clone(), token.clone());
+        if min > 0 && amount < min {
+            return Err(Error::AmountBelowMinimum);
+        }
+        if max > 0 && amount > max {
+            return Err(Error::AmountAboveMaximum);
+        }
+        Ok(())
+    }
+
+    fn set_token_limit(env: &Env, key: &Symbol, token: Address, value: i128) {
+        let mut limits: Map<Address, i128> =
+            env.storage().instance().get(key).unwrap_or(Map::new(env));
+        limits.set(token, value);
+        env.storage().instance().set(key, &limits);
+    }
+
+    /// Shared disbursement path: pays out whatever is left after partial claims to
+    /// `target` (defaults to the recipient). Returns the package, the gross amount released
+    /// and the net amount transferred; the caller unlocks the net amount.
+    /// `honor_cliff` rejects packages whose `start_at` has not been reached yet.
+    fn disburse_internal(
+        env: &Env,
+        actor: &Address,
+        id: u64,
+        target: Option<&Address>,
+        honor_cliff: bool,
+    ) -> Result<(Package, i128, i128), Error> {
+        let key = (symbol_short!("pkg"), id);
+        let mut package: Package = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .ok_or(Error::PackageNotFound)?;
+
+        if !package.status.is_active() {
+            return Err(Error::PackageNotActive);
+        }
+        if honor_cliff && env.ledger().timestamp() < package.start_at {
+            return Err(Error::PackageNotYetClaimable);
+        }
+
+        // State Transition
+        let amount = package.remaining_amount;
+        Self::set_status(env, &mut package, PackageStatus::Disbursed, actor);
+        package.remaining_amount = 0;
+        env.storage().persistent().set(&key, &package);
+
+        // Transfer (net of fees)
+        let to = target.unwrap_or(&package.recipient);
+        let net = Self::transfer_net_of_fee(env, &package.token, to, amount);
+        Self::add_to_total(env, &KEY_TOTAL_DISBURSED, &package.token, amount);
+
+        Ok((package, amount, net))
+    }
+
+    /// Pays out package `id` to `target` instead of its recipient, ignoring the cliff.
+    fn disburse_to_internal(
+        env: &Env,
+        admin: Address,
+        id: u64,
+        target: Address,
+    ) -> Result<(), Error> {
+        let (package, amount, net) =
+            Self::disburse_internal(env, &admin, id, Some(&target), false)?;
+        Self::decrement_locked(env, &package.token, net);
+
+        Self::log_admin_action(env, symbol_short!("disb_to"), Some(id), &admin, "");
+
+        DisbursedToEvent {
+            id,
+            admin,
+            original_recipient: package.recipient,
+            target,
+            amount,
+        }
+        .publish(env);
+
+        Ok(())
+    }
+
+    /// Moves an active package to `Cancelled`. The caller unlocks `remaining_amount`.
+    fn revoke_internal(env: &Env, actor: &Address, id: u64) -> Result<Package, Error> {
+        Self::close_package(env, actor, id, PackageStatus::Cancelled)
+    }
+
+    /// Moves an active package to the terminal `status` and persists it.
+    fn close_package(
+        env: &Env,
+        actor: &Address,
+        id: u64,
+        status: PackageStatus,
+    ) -> Result<Package, Error> {
+        let key = (symbol_short!("pkg"), id);
+        let mut package: Package = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .ok_or(Error::PackageNotFound)?;
+
+        if !package.status.is_active() {
+            return Err(Error::InvalidState);
+        }
+
+        // State Transition
+        Self::set_status(env, &mut package, status, actor);
+        env.storage().persistent().set(&key, &package);
+
+        Ok(package)
+    }
+
+    /// Cancels an active package whose funds move into new packages (split/merge).
+    /// The funds are unlocked and the package is emptied so it can never be refunded.
+    /// Returns the package as it was before being emptied.
+    fn retire_package(env: &Env, actor: &Address, id: u64) -> Result<Package, Error> {
+        let source = Self::revoke_internal(env, actor, id)?;
+        Self::decrement_locked(env, &source.token, source.remaining_amount);
+        Self::add_to_total(
+            env,
+            &KEY_TOTAL_CANCELLED,
+            &source.token,
+            -source.remaining_amount,
+        );
+
+        let mut emptied = source.clone();
+        emptied.remaining_amount = 0;
+        env.storage()
+            .persistent()
+            .set(&(symbol_short!("pkg"), id), &emptied);
+        Ok(source)
+    }
+
+    /// Moves an active, past-deadline package to `Expired`. The caller unlocks `remaining_amount`.
+    fn force_expire_internal(env: &Env, actor: &Address, id: u64) -> Result<Package, Error> {
+        let key = (symbol_short!("pkg"), id);
+        let mut package: Package = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .ok_or(Error::PackageNotFound)?;
+
+        if !package.status.is_active() {
+            return Err(Error::InvalidState);
+        }
+        let expiry = Self::effective_expiry(env, &package);
+        if expiry == 0 || env.ledger().timestamp() <= expiry {
+            return Err(Error::PackageNotExpired);
+        }
+
+        Self::set_status(env, &mut package, PackageStatus::Expired, actor);
+        env.storage().persistent().set(&key, &package);
+
+        Ok(package)
+    }
+
+    /// Refunds `remaining_amount` of an expired or cancelled package to `admin`.
+    fn refund_internal(env: &Env, admin: Address, id: u64) -> Result<(), Error> {
+        let key = (symbol_short!("pkg"), id);
+        let mut package: Package = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .ok_or(Error::PackageNotFound)?;
+
+        // Can only refund if Expired or Cancelled.
+        // If Created/PartiallyClaimed, must Revoke first. If Claimed/Disbursed, impossible.
+        // If Refunded or Transferred, impossible.
+        // Only the unclaimed `remaining_amount` is ever refunded.
+        if package.status.is_active() {
+            // Check if actually expired (honouring the global override)
+            let expiry = Self::effective_expiry(env, &package);
+            if expiry > 0 && env.ledger().timestamp() > expiry {
+                Self::set_status(env, &mut package, PackageStatus::Expired, &admin);
+                // If we just expired it, we need to unlock the funds first
+                Self::decrement_locked(env, &package.token, package.remaining_amount);
+            } else {
+                return Err(Error::InvalidState); // Must revoke first
+            }
+        } else if !matches!(
+            package.status,
+            PackageStatus::Cancelled | PackageStatus::Expired
+        ) {
+            return Err(Error::InvalidState);
+        }
+
+        // If Cancelled, funds were already unlocked in `revoke`.
+        // If Expired (logic above), funds were just unlocked.
+
+        // State Transition
+        Self::set_status(env, &mut package, PackageStatus::Refunded, &admin);
+        env.storage().persistent().set(&key, &package);
+
+        // Transfer Contract -> refund destination (Admin unless overridden)
+        let destination: Address = env
+            .storage()
+            .instance()
+            .get(&KEY_REFUND_DEST)
+            .unwrap_or(admin.clone());
+        let token_client = token::Client::new(env, &package.token);
+        token_client.transfer(
+            &env.current_contract_address(),
+            &destination,
+            &package.remaining_amount,
+        );
+        Self::add_to_total(
+            env,
+            &KEY_TOTAL_REFUNDED,
+            &package.token,
+            package.remaining_amount,
+        );
+        Self::log_admin_action(env, symbol_short!("refund"), Some(id), &admin, "");
+
+        RefundedEvent {
+            id,
+            admin,
+            destination,
+            amount: package.remaining_amount,
+        }
+        .publish(env);
+
+        Ok(())
+    }
+
+    /// Pays out un-earmarked funds to `destination`, or to the admin when `None`.
+    fn withdraw_excess_internal(
+        env: &Env,
+        admin: Address,
+        token: Address,
+        amount: i128,
+        destination: Option<Address>,
+    ) -> Result<(), Error> {
+        if amount <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+        if Self::withdrawable_balance(env, &token) < amount {
+            return Err(Error::InsufficientFunds);
+        }
+
+        let to = destination.clone().unwrap_or(admin.clone());
+        let token_client = token::Client::new(env, &token);
+        token_client.transfer(&env.current_contract_address(), &to, &amount);
+        Self::log_admin_action(env, symbol_short!("withdraw"), None, &admin, "");
+
+        match destination {
+            Some(destination) => ExcessWithdrawnEvent {
+                admin,
+                token,
+                destination,
+                amount,
+            }
+            .publish(env),
+            None => PoolWithdrawnEvent {
+                admin,
+                token,
+                amount,
+            }
+            .publish(env),
+        }
+
+        Ok(())
+    }
+
+    /// Balance that backs neither an active package nor a pending refund: funds of
+    /// cancelled and expired packages stay in the contract until `refund` pays them out.
+    fn withdrawable_balance(env: &Env, token: &Address) -> i128 {
+        let pending_refunds = Self::read_totals(env, &KEY_TOTAL_CANCELLED)
+            .get(token.clone())
+            .unwrap_or(0);
+        Self::get_available_balance(env.clone(), token.clone()) - pending_refunds
+    }
+
+    fn transfer_admin_internal(env: &Env, admin: Address, new_admin: Address) {
+        env.storage().instance().set(&KEY_PENDING_ADMIN, &new_admin);
+        env.storage()
+            .instance()
+            .set(&KEY_ADMIN_PROPOSED_AT, &env.ledger().timestamp());
+        Self::log_admin_action(env, symbol_short!("xfer_adm"), None, &admin, "");
+
+        AdminTransferProposedEvent { admin, new_admin }.publish(env);
+    }
+
+    /// Emits `ContractUpgradedEvent` and swaps the contract code; the new code runs
+    /// from the next invocation on.
+    fn upgrade_internal(
+        env: &Env,
+        admin: Address,
+        new_wasm_hash: BytesN<32>,
+        new_version: Option<String>,
+    ) {
+        let old_hash = env
+            .storage()
+            .instance()
+            .get(&KEY_WASM_HASH)
+            .unwrap_or(BytesN::from_array(env, &[0; 32]));
+        env.storage().instance().set(&KEY_WASM_HASH, &new_wasm_hash);
+        if let Some(version) = new_version {
+            env.storage().instance().set(&KEY_VERSION, &version);
+        }
+        Self::log_admin_action(env, symbol_short!("upgrade"), None, &admin, "");
+
+        ContractUpgradedEvent {
+            old_hash,
+            new_hash: new_wasm_hash.clone(),
+            admin,
+        }
+        .publish(env);
+
+        env.deployer().update_current_contract_wasm(new_wasm_hash);
+    }
+
+    /// Cancels an active package and returns its remaining funds to the pool.
+    fn revoke_and_unlock(env: &Env, admin: Address, id: u64) -> Result<(), Error> {
+        let package = Self::revoke_internal(env, &admin, id)?;
+
+        // Unlock funds (return to pool)
+        Self::decrement_locked(env, &package.token, package.remaining_amount);
+        Self::log_admin_action(env, symbol_short!("revoke"), Some(id), &admin, "");
+
+        RevokedEvent {
+            id,
+            admin,
+            amount: package.remaining_amount,
+        }
+        .publish(env);
+
+        Ok(())
+    }
+
+    /// Appends an entry to the admin log. Entries are never modified or removed.
+    fn log_admin_action(
+        env: &Env,
+        action: Symbol,
+        package_id: Option<u64>,
+        actor: &Address,
+        details: &str,
+    ) {
+        let index = Self::get_admin_log_count(env.clone());
+        Self::store_index(
+            env,
+            &(KEY_ADMIN_LOG, index),
+            &AdminLogEntry {
+                action,
+                package_id,
+                timestamp: env.ledger().timestamp(),
+                actor: actor.clone(),
+                details: String::from_str(env, details),
+            },
+        );
+        env.storage()
+            .instance()
+            .set(&KEY_ADMIN_LOG_COUNT, &(index + 1));
+    }
+
+    fn require_not_paused(env: &Env) -> Result<(), Error> {
+        if Self::is_paused(env.clone()) {
+            return Err(Error::ContractPaused);
+        }
+        Ok(())
+    }
+
+    fn set_multisig_internal(
+        env: &Env,
+        admin: Address,
+        signers: Vec<Address>,
+        threshold: u32,
+    ) -> Result<(), Error> {
+        if signers.is_empty() && threshold == 0 {
+            env.storage().instance().remove(&KEY_SIGNERS);
+            env.storage().instance().remove(&KEY_THRESHOLD);
+        } else {
+            if threshold == 0 || threshold > signers.len() {
+                return Err(Error::InvalidThreshold);
+            }
+            env.storage().instance().set(&KEY_SIGNERS, &signers);
+            env.storage().instance().set(&KEY_THRESHOLD, &threshold);
+        }
+
+        Self::log_admin_action(env, symbol_short!("multisig"), None, &admin, "");
+        Ok(())
+    }
+
+    /// Admin auth for actions covered by `AdminAction`; rejected while multisig is configured.
+    fn require_single_admin(env: &Env) -> Result<Address, Error> {
+        let admin = Self::get_admin(env.clone())?;
+        if Self::get_multisig_threshold(env.clone()) > 0 {
+            return Err(Error::MultisigRequired);
+        }
+        admin.require_auth();
+        Ok(admin)
+    }
+
+    fn require_signer(env: &Env, signer: &Address) -> Result<(), Error> {
+        if !Self::get_multisig_signers(env.clone()).contains(signer) {
+            return Err(Error::NotAuthorized);
+        }
+        signer.require_auth();
+        Ok(())
+    }
+
+    /// Executes `proposal` once it has enough approvals, then persists it.
+    fn store_or_execute_proposal(env: &Env, mut proposal: Proposal) -> Result<(), Error> {
+        if proposal.approvals.len() >= Self::get_multisig_threshold(env.clone()) {
+            Self::execute_proposal(env, &proposal)?;
+            proposal.executed = true;
+            ProposalExecutedEvent {
+                proposal_id: proposal.id,
+            }
+            .publish(env);
+        }
+
+        env.storage()
+            .persistent()
+            .set(&(symbol_short!("prop"), proposal.id), &proposal);
+        Ok(())
+    }
+
+    /// Runs an approved proposal on behalf of the current admin.
+    fn execute_proposal(env: &Env, proposal: &Proposal) -> Result<(), Error> {
+        let admin = Self::get_admin(env.clone())?;
+        let params = proposal.params.clone();
+        match proposal.action {
+            AdminAction::Refund => {
+                Self::require_not_paused(env)?;
+                Self::refund_internal(env, admin, params.package_id)
+            }
+            AdminAction::Revoke => {
+                Self::require_not_paused(env)?;
+                Self::revoke_and_unlock(env, admin, params.package_id)
+            }
+            AdminAction::WithdrawExcess => {
+                Self::require_not_paused(env)?;
+                let token = params.token.ok_or(Error::InvalidState)?;
+                Self::withdraw_excess_internal(env, admin, token, params.amount, params.address)
+            }
+            AdminAction::TransferAdmin => {
+                let new_admin = params.address.ok_or(Error::InvalidState)?;
+                Self::transfer_admin_internal(env, admin, new_admin);
+                Ok(())
+            }
+            AdminAction::Upgrade => {
+                let wasm_hash = params.wasm_hash.ok_or(Error::InvalidState)?;
+                Self::upgrade_internal(env, admin, wasm_hash, params.version);
+                Ok(())
+            }
+            AdminAction::SetMultisig => {
+                Self::set_multisig_internal(env, admin, params.signers, params.threshold)
+            }
+            AdminAction::DisburseTo => {
+                Self::require_not_paused(env)?;
+                let target = params.address.ok_or(Error::InvalidState)?;
+                Self::disburse_to_internal(env, admin, params.package_id, target)
+            }
+        }
+    }
+
+    /// Authorizes `caller` as the admin or a registered operator.
+    fn require_creator(env: &Env, caller: &Address) -> Result<(), Error> {
+        let admin = Self::get_admin(env.clone())?;
+        if *caller != admin && !Self::is_operator(env.clone(), caller.clone()) {
+            return Err(Error::NotAuthorized);
+        }
+        caller.require_auth();
+        Ok(())
+    }
+
+    fn token_whitelist(env: &Env) -> Map<Address, bool> {
+        env.storage()
+            .instance()
+            .get(&KEY_TOKEN_WHITELIST)
+            .unwrap_or(Map::new(env))
+    }
+
+    fn operators_map(env: &Env) -> Map<Address, bool> {
+        env.storage()
+            .persistent()
+            .get(&KEY_OPERATORS)
+            .unwrap_or(Map::new(env))
+    }
+
+    /// Releases `amount` of locked package funds to `to`, withholding the configured fee.
+    /// The fee portion stays locked and is moved to the fee accumulator.
+    /// Returns the net amount transferred.
+    fn pay_out(env: &Env, token: &Address, to: &Address, amount: i128) -> i128 {
+        let net = Self::transfer_net_of_fee(env, token, to, amount);
+        Self::decrement_locked(env, token, net);
+        net
+    }
+
+    /// Fee accounting and transfer half of `pay_out`; the caller unlocks the returned net amount.
+    fn transfer_net_of_fee(env: &Env, token: &Address, to: &Address, amount: i128) -> i128 {
+        let fee = Self::fee_for(env, token, amount);
+        let net = amount - fee;
+        Self::add_to_total(env, &KEY_TOTAL_CLAIMED, token, net);
+
+        if fee > 0 {
+            let mut fees: Map<Address, i128> = env
+                .storage()
+                .instance()
+                .get(&KEY_FEES_ACCRUED)
+                .unwrap_or(Map::new(env));
+            let accrued = fees.get(token.clone()).unwrap_or(0);
+            fees.set(token.clone(), accrued + fee);
+            env.storage().instance().set(&KEY_FEES_ACCRUED, &fees);
+        }
+
+        if net > 0 {
+            let token_client = token::Client::new(env, token);
+            token_client.transfer(&env.current_contract_address(), to, &net);
+        }
+
+        net
+    }
+
+    fn fee_for(env: &Env, token: &Address, amount: i128) -> i128 {
+        let rate_bps = Self::get_fee_rate_bps(env.clone(), token.clone());
+        amount * rate_bps as i128 / BPS_DENOMINATOR
+    }
+
+    /// Earmarks `amount` of `token`, failing if the unlocked balance cannot cover it.
+    fn lock_funds(env: &Env, token: &Address, amount: i128) -> Result<(), Error> {
+        let token_client = token::Client::new(env, token);
+        let contract_balance = token_client.balance(&env.current_contract_address());
+
+        let mut locked_map: Map<Address, i128> = env
+            .storage()
+            .instance()
+            .get(&KEY_TOTAL_LOCKED)
+            .unwrap_or(Map::new(env));
+        let current_locked = locked_map.get(token.clone()).unwrap_or(0);
+
+        // Ensure we don't over-promise funds (an overflowing total can never be backed)
+        let new_locked = current_locked
+            .checked_add(amount)
+            .filter(|total| *total <= contract_balance)
+            .ok_or(Error::InsufficientFunds)?;
+
+        locked_map.set(token.clone(), new_locked);
+        env.storage().instance().set(&KEY_TOTAL_LOCKED, &locked_map);
+        Ok(())
+    }
+
+    fn decrement_locked(env: &Env, token: &Address, amount: i128) {
+        let mut locked_map: Map<Address, i128> = env
+            .storage()
+            .instance()
+            .get(&KEY_TOTAL_LOCKED)
+            .unwrap_or(Map::new(env));
+
+        let current = locked_map.get(token.clone()).unwrap_or(0);
+        let new_locked = if current > amount {
+            current - amount
+        } else {
+            0
+        };
+
+        locked_map.set(token.clone(), new_locked);
+        env.storage().instance().set(&KEY_TOTAL_LOCKED, &locked_map);
+    }
+
+    pub fn get_package(env: Env, id: u64) -> Result<Package, Error> {
+        let key = (symbol_short!("pkg"), id);
+        env.storage()
+            .persistent()
             .get(&key)
             .ok_or(Error::PackageNotFound)
     }
+
+    /// Seconds since the package was created.
+    pub fn get_package_age(env: Env, id: u64) -> Result<u64, Error> {
+        let package = Self::get_package(env.clone(), id)?;
+        Ok(env.ledger().timestamp().saturating_sub(package.created_at))
+    }
+
+    /// Seconds until `expires_at`: `None` without an expiry, `Some(0)` once it has passed.
+    pub fn get_time_remaining(env: Env, id: u64) -> Result<Option<u64>, Error> {
+        let package = Self::get_package(env.clone(), id)?;
+        if package.expires_at == 0 {
+            return Ok(None);
+        }
+        Ok(Some(
+            package.expires_at.saturating_sub(env.ledger().timestamp()),
+        ))
+    }
+
+    /// Every status change the package has gone through, oldest first.
+    pub fn get_package_history(env: Env, id: u64) -> Result<Vec<StatusTransition>, Error> {
+        Self::get_package(env.clone(), id)?;
+        Ok(env
+            .storage()
+            .persistent()
+            .get(&(symbol_short!("hist"), id))
+            .unwrap_or(Vec::new(&env)))
+    }
+
+    /// Seconds from creation to the package's last terminal transition (for a refunded
+    /// package, the refund). `None` while the package is still active.
+    pub fn get_package_lifecycle_duration(env: Env, id: u64) -> Result<Option<u64>, Error> {
+        let package = Self::get_package(env.clone(), id)?;
+        if package.status.is_active() {
+            return Ok(None);
+        }
+
+        // The history's last entry is the transition into the current terminal status
+        let completed_at = Self::get_package_history(env, id)?
+            .last()
+            .map(|transition| transition.timestamp)
+            .unwrap_or(package.created_at);
+        Ok(Some(completed_at.saturating_sub(package.created_at)))
+    }
+
+    /// Active `token` packages whose effective expiry falls within the next `seconds`
+    /// (including ones already past it but not yet expired on-chain), grouped by week
+    /// of `expires_at` and ordered by ID within each week. Once the global override is
+    /// within the window, every expiring package is included.
+    pub fn get_packages_expiring_within(env: Env, token: Address, seconds: u64) -> Vec<u64> {
+        let cutoff = env.ledger().timestamp().saturating_add(seconds);
+        let global = Self::get_global_expiry(env.clone());
+        let all_due = global > 0 && global <= cutoff;
+        let mut ids = Vec::new(&env);
+        for bucket in Self::expiry_buckets(&env, &token).iter() {
+            if !all_due && bucket.saturating_mul(EXPIRY_BUCKET_SECONDS) > cutoff {
+                break;
+            }
+            for (id, expires_at) in Self::expiry_bucket(&env, &token, bucket).iter() {
+                if all_due || expires_at <= cutoff {
+                    ids.push_back(id);
+                }
+            }
+        }
+        ids
+    }
+
+    /// Looks up a package by the off-chain reference it was created with.
+    pub fn get_package_by_external_ref(env: Env, external_ref: String) -> Result<Package, Error> {
+        let id = env
+            .storage()
+            .persistent()
+            .get(&(KEY_EXT_REF_INDEX, external_ref))
+            .ok_or(Error::PackageNotFound)?;
+        Self::get_package(env, id)
+    }
+
+    /// Every package ID in creation order, `limit` at a time starting at `offset`.
+    pub fn get_all_package_ids_paginated(env: Env, offset: u32, limit: u32) -> Vec<u64> {
+        Self::id_list_slice(&env, &KEY_ALL_PKG_IDS, offset, limit)
+    }
+
+    /// Length of the package ID list served by `get_all_package_ids_paginated`.
+    pub fn get_total_package_count(env: Env) -> u32 {
+        Self::id_list_len(&env, &KEY_ALL_PKG_IDS)
+    }
+
+    /// Every package ever created in `token`, terminal ones included, oldest first.
+    pub fn get_packages_by_token(env: Env, token: Address, offset: u32, limit: u32) -> Vec<u64> {
+        Self::id_list_slice(&env, &(KEY_TOKEN_PKG_INDEX, token), offset, limit)
+    }
+
+    /// Length of the list served by `get_packages_by_token`.
+    pub fn get_package_count_for_token(env: Env, token: Address) -> u32 {
+        Self::id_list_len(&env, &(KEY_TOKEN_PKG_INDEX, token))
+    }
+
+    /// Total number of packages ever created.
+    pub fn get_package_count(env: Env) -> u64 {
+        Self::read_counter(&env, &KEY_PKG_COUNT)
+    }
+
+    /// Number of packages currently `Claimed`.
+    pub fn get_claimed_count(env: Env) -> u64 {
+        Self::read_counter(&env, &KEY_CNT_CLAIMED)
+    }
+
+    /// Number of packages currently `Disbursed` by the admin.
+    pub fn get_disbursed_count(env: Env) -> u64 {
+        Self::read_counter(&env, &KEY_CNT_DISBURSED)
+    }
+
+    /// Number of packages currently `Cancelled` (revoked but not yet refunded).
+    pub fn get_cancelled_count(env: Env) -> u64 {
+        Self::read_counter(&env, &KEY_CNT_CANCELLED)
+    }
+
+    /// Number of packages currently `Expired` (not yet refunded).
+    pub fn get_expired_count(env: Env) -> u64 {
+        Self::read_counter(&env, &KEY_CNT_EXPIRED)
+    }
+
+    /// Number of packages currently `Refunded`.
+    pub fn get_refunded_count(env: Env) -> u64 {
+        Self::read_counter(&env, &KEY_CNT_REFUNDED)
+    }
+
+    /// Number of packages moved to another escrow (`Transferred`).
+    pub fn get_transferred_count(env: Env) -> u64 {
+        Self::read_counter(&env, &KEY_CNT_TRANSFERRED)
+    }
+
+    /// Amount of `token` currently earmarked for packages (and uncollected fees).
+    pub fn get_total_locked(env: Env, token: Address) -> i128 {
+        let locked_map: Map<Address, i128> = env
+            .storage()
+            .instance()
+            .get(&KEY_TOTAL_LOCKED)
+            .unwrap_or(Map::new(&env));
+        locked_map.get(token).unwrap_or(0)
+    }
+
+    /// Unclaimed amount of `token` held in `recipient`'s active packages.
+    pub fn get_recipient_total_locked(env: Env, recipient: Address, token: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&(KEY_RECIPIENT_LOCKED, recipient, token))
+            .unwrap_or(0)
+    }
+
+    /// Contract balance of `token` that is free to back new packages.
+    pub fn get_available_balance(env: Env, token: Address) -> i128 {
+        let token_client = token::Client::new(&env, &token);
+        let contract_balance = token_client.balance(&env.current_contract_address());
+        contract_balance - Self::get_total_locked(env, token)
+    }
+
+    /// Admin heartbeat: publishes a `HealthSnapshotEvent` covering every token that has
+    /// ever had funds locked. Does not modify any state.
+    pub fn emit_health_snapshot(env: Env) -> Result<(), Error> {
+        let admin = Self::get_admin(env.clone())?;
+        admin.require_auth();
+
+        let total_locked: Map<Address, i128> = env
+            .storage()
+            .instance()
+            .get(&KEY_TOTAL_LOCKED)
+            .unwrap_or(Map::new(&env));
+        let mut contract_balance: Map<Address, i128> = Map::new(&env);
+        for token in total_locked.keys().iter() {
+            let balance = token::Client::new(&env, &token).balance(&env.current_contract_address());
+            contract_balance.set(token, balance);
+        }
+
+        let total_packages = Self::read_counter(&env, &KEY_PKG_COUNT);
+        let terminal = Self::read_counter(&env, &KEY_CNT_CLAIMED)
+            + Self::read_counter(&env, &KEY_CNT_DISBURSED)
+            + Self::read_counter(&env, &KEY_CNT_CANCELLED)
+            + Self::read_counter(&env, &KEY_CNT_EXPIRED)
+            + Self::read_counter(&env, &KEY_CNT_REFUNDED)
+            + Self::read_counter(&env, &KEY_CNT_TRANSFERRED);
+
+        HealthSnapshotEvent {
+            timestamp: env.ledger().timestamp(),
+            admin,
+            total_packages,
+            active_packages: total_packages.saturating_sub(terminal),
+            total_locked,
+            contract_balance,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Share of the `token` balance that is locked, in basis points (`10_000` = fully utilized,
+    /// also reported when locked funds exceed the balance).
+    /// Errors with `InsufficientFunds` if funds are locked but the balance is zero.
+    pub fn get_locked_ratio_bps(env: Env, token: Address) -> Result<u32, Error> {
+        let balance = token::Client::new(&env, &token).balance(&env.current_contract_address());
+        let locked = Self::get_total_locked(env, token);
+        if balance == 0 {
+            return if locked == 0 {
+                Ok(0)
+            } else {
+                Err(Error::InsufficientFunds)
+            };
+        }
+        // Underfunded pools report fully utilized rather than more than 100%
+        let ratio = locked
+            .checked_mul(BPS_DENOMINATOR)
+            .ok_or(Error::InvalidAmount)?
+            / balance;
+        u32::try_from(ratio.min(BPS_DENOMINATOR)).map_err(|_| Error::InvalidAmount)
+    }
+
+    /// Live check of whether the contract's `token` balance still covers everything locked
+    /// in that token. Active packages get `Solvent`/`Underfunded`; terminal ones `NotApplicable`.
+    pub fn get_package_solvency_status(env: Env, id: u64) -> Result<SolvencyStatus, Error> {
+        let package = Self::get_package(env.clone(), id)?;
+        if !package.status.is_active() {
+            return Ok(SolvencyStatus::NotApplicable);
+        }
+
+        let balance =
+            token::Client::new(&env, &package.token).balance(&env.current_contract_address());
+        if balance >= Self::get_total_locked(env, package.token) {
+            Ok(SolvencyStatus::Solvent)
+        } else {
+            Ok(SolvencyStatus::Underfunded)
+        }
+    }
+
+    /// Aggregates the running totals for `token` and the global package counters.
+    pub fn get_stats(env: Env, token: Address) -> ContractStats {
+        ContractStats {
+            total_funded: Self::read_totals(&env, &KEY_TOTAL_FUNDED)
+                .get(token.clone())
+                .unwrap_or(0),
+            total_disbursed: Self::read_totals(&env, &KEY_TOTAL_DISBURSED)
+                .get(token.clone())
+                .unwrap_or(0),
+            total_refunded: Self::read_totals(&env, &KEY_TOTAL_REFUNDED)
+                .get(token.clone())
+                .unwrap_or(0),
+            total_locked: Self::get_total_locked(env.clone(), token.clone()),
+            available_balance: Self::get_available_balance(env.clone(), token),
+            packages_created: Self::read_counter(&env, &KEY_PKG_COUNT),
+            packages_claimed: Self::read_counter(&env, &KEY_CNT_CLAIMED),
+            packages_disbursed: Self::read_counter(&env, &KEY_CNT_DISBURSED),
+            packages_cancelled: Self::read_counter(&env, &KEY_CNT_CANCELLED),
+            packages_expired: Self::read_counter(&env, &KEY_CNT_EXPIRED),
+            packages_refunded: Self::read_counter(&env, &KEY_CNT_REFUNDED),
+            packages_transferred: Self::read_counter(&env, &KEY_CNT_TRANSFERRED),
+        }
+    }
+
+    /// Where every unit of `token` that entered the pool currently stands.
+    pub fn get_token_breakdown(env: Env, token: Address) -> Result<TokenBreakdown, Error> {
+        let total = |key: &Symbol| Self::read_totals(&env, key).get(token.clone()).unwrap_or(0);
+        Ok(TokenBreakdown {
+            total_locked: Self::get_total_locked(env.clone(), token.clone()),
+            total_claimed: total(&KEY_TOTAL_CLAIMED),
+            total_refunded: total(&KEY_TOTAL_REFUNDED),
+            total_cancelled: total(&KEY_TOTAL_CANCELLED),
+            total_funded: total(&KEY_TOTAL_FUNDED),
+            available: Self::withdrawable_balance(&env, &token),
+        })
+    }
+
+    /// Returns the full contract configuration in a single read.
+    pub fn get_config(env: Env) -> Result<ContractConfig, Error> {
+        let storage = env.storage().instance();
+        Ok(ContractConfig {
+            admin: Self::get_admin(env.clone())?,
+            paused: Self::is_paused(env.clone()),
+            token_whitelist_enabled: storage.get(&KEY_TOKEN_WL_ENABLED).unwrap_or(false),
+            default_expiry_duration: Self::get_default_expiry_duration(env.clone()),
+            allowlist_mode: storage.get(&KEY_ALLOWLIST_MODE).unwrap_or(false),
+            fee_rates: storage.get(&KEY_FEE_RATES).unwrap_or(Map::new(&env)),
+        })
+    }
+
+    /// Up to `limit` admin log entries, oldest first, starting at `offset`.
+    pub fn get_admin_log(env: Env, offset: u64, limit: u32) -> Vec<AdminLogEntry> {
+        let end = Self::get_admin_log_count(env.clone()).min(offset.saturating_add(limit as u64));
+
+        let mut entries = Vec::new(&env);
+        for index in offset..end {
+            if let Some(entry) = env.storage().persistent().get(&(KEY_ADMIN_LOG, index)) {
+                entries.push_back(entry);
+            }
+        }
+        entries
+    }
+
+    pub fn get_admin_log_count(env: Env) -> u64 {
+        env.storage()
+            .instance()
+            .get(&KEY_ADMIN_LOG_COUNT)
+            .unwrap_or(0)
+    }
 }
@@ -0,0 +1,126 @@
+#![cfg(test)]
+
+use aid_escrow::{AidEscrow, AidEscrowClient, Error, PackageStatus};
+use soroban_sdk::{
+    Address, Env, IntoVal,
+    testutils::{Address as _, Ledger, MockAuth, MockAuthInvoke},
+    token::StellarAssetClient,
+};
+
+/// Funded contract with one operator and package 1 (100 units, expiring at 2000).
+/// Returns `(client, admin, operator, recipient, token)`.
+fn setup(env: &Env) -> (AidEscrowClient<'static>, Address, Address, Address, Address) {
+    env.mock_all_auths();
+
+    let admin = Address::generate(env);
+    let operator = Address::generate(env);
+    let recipient = Address::generate(env);
+    let token_admin = Address::generate(env);
+    let token = env
+        .register_stellar_asset_contract_v2(token_admin)
+        .address();
+
+    let contract_id = env.register(AidEscrow, ());
+    let client = AidEscrowClient::new(env, &contract_id);
+    client.init(&admin);
+    client.add_operator(&operator);
+
+    StellarAssetClient::new(env, &token).mint(&admin, &1000);
+    client.fund(&token, &admin, &1000);
+
+    env.ledger().set_timestamp(1000);
+    client.create_package(
+        &admin, &1, &recipient, &100, &token, &2000, &0, &None, &None,
+    );
+
+    (client, admin, operator, recipient, token)
+}
+
+/// Makes `signer` the only address that has authorized `fn_name(args)` on the contract.
+fn sign_as(env: &Env, client: &AidEscrowClient, signer: &Address, fn_name: &str, id: u64) {
+    env.mock_auths(&[MockAuth {
+        address: signer,
+        invoke: &MockAuthInvoke {
+            contract: &client.address,
+            fn_name,
+            args: (id,).into_val(env),
+            sub_invokes: &[],
+        },
+    }]);
+}
+
+#[test]
+fn test_operator_cannot_refund() {
+    let env = Env::default();
+    let (client, _, operator, _, token) = setup(&env);
+
+    env.ledger().set_timestamp(2001);
+    sign_as(&env, &client, &operator, "refund", 1);
+    // Rejected by the host's admin auth check rather than with a contract error
+    assert!(matches!(client.try_refund(&1), Err(Err(_))));
+
+    env.mock_all_auths();
+    assert_eq!(client.get_package(&1).status, PackageStatus::Created);
+    assert_eq!(client.get_total_locked(&token), 100);
+}
+
+#[test]
+fn test_operator_cannot_revoke() {
+    let env = Env::default();
+    let (client, _, operator, _, token) = setup(&env);
+
+    sign_as(&env, &client, &operator, "revoke", 1);
+    // Rejected by the host's admin auth check rather than with a contract error
+    assert!(matches!(client.try_revoke(&1), Err(Err(_))));
+
+    env.mock_all_auths();
+    assert_eq!(client.get_package(&1).status, PackageStatus::Created);
+    assert_eq!(client.get_total_locked(&token), 100);
+}
+
+#[test]
+fn test_operator_can_create_package() {
+    let env = Env::default();
+    let (client, _, operator, recipient, token) = setup(&env);
+
+    let id = client.create_package(
+        &operator, &2, &recipient, &100, &token, &0, &0, &None, &None,
+    );
+    assert_eq!(id, 2);
+    assert_eq!(client.get_package(&2).status, PackageStatus::Created);
+    assert_eq!(client.get_total_locked(&token), 200);
+}
+
+#[test]
+fn test_non_operator_cannot_create_package() {
+    let env = Env::default();
+    let (client, _, _, recipient, token) = setup(&env);
+    let outsider = Address::generate(&env);
+
+    assert_eq!(
+        client.try_create_package(
+            &outsider, &2, &recipient, &100, &token, &0, &0, &None, &None
+        ),
+        Err(Ok(Error::NotAuthorized))
+    );
+    assert_eq!(client.try_get_package(&2), Err(Ok(Error::PackageNotFound)));
+}
+
+#[test]
+fn test_removed_operator_cannot_create_package() {
+    let env = Env::default();
+    let (client, _, operator, recipient, token) = setup(&env);
+
+    client.create_package(
+        &operator, &2, &recipient, &100, &token, &0, &0, &None, &None,
+    );
+    client.remove_operator(&operator);
+
+    assert_eq!(
+        client.try_create_package(
+            &operator, &3, &recipient, &100, &token, &0, &0, &None, &None
+        ),
+        Err(Ok(Error::NotAuthorized))
+    );
+    assert_eq!(client.get_total_locked(&token), 200);
+}
@@ -0,0 +1,670 @@
+#![cfg(test)]
+
+use aid_escrow::{ActionParams, AdminAction, AidEscrow, AidEscrowClient, Error, PackageStatus};
+use soroban_sdk::{
+    Address, BytesN, Env, IntoVal, String, symbol_short,
+    testutils::{Address as _, Ledger, MockAuth, MockAuthInvoke},
+    token::{StellarAssetClient, TokenClient},
+    vec,
+};
+
+fn setup_token(env: &Env, admin: &Address) -> (TokenClient<'static>, StellarAssetClient<'static>) {
+    let token_contract = env.register_stellar_asset_contract_v2(admin.clone());
+    let token_client = TokenClient::new(env, &token_contract.address());
+    let token_admin_client = StellarAssetClient::new(env, &token_contract.address());
+    (token_client, token_admin_client)
+}
+
+fn setup(env: &Env) -> (AidEscrowClient<'static>, Address) {
+    let admin = Address::generate(env);
+    let contract_id = env.register(AidEscrow, ());
+    let client = AidEscrowClient::new(env, &contract_id);
+    client.init(&admin);
+    (client, admin)
+}
+
+#[test]
+fn test_admin_transfer_two_step() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin) = setup(&env);
+    let new_admin = Address::generate(&env);
+
+    // Nothing to accept yet
+    assert_eq!(client.try_accept_admin(), Err(Ok(Error::NoPendingAdmin)));
+
+    // Propose: original admin stays in control
+    client.transfer_admin(&new_admin);
+    assert_eq!(client.get_pending_admin(), Some(new_admin.clone()));
+    assert_eq!(client.get_admin(), admin);
+
+    // Accept: transfer goes live and pending slot is cleared
+    client.accept_admin();
+    assert_eq!(client.get_admin(), new_admin);
+    assert_eq!(client.get_pending_admin(), None);
+}
+
+#[test]
+fn test_admin_transfer_cancel() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin) = setup(&env);
+    let new_admin = Address::generate(&env);
+
+    assert_eq!(
+        client.try_cancel_admin_transfer(),
+        Err(Ok(Error::NoPendingAdmin))
+    );
+
+    client.transfer_admin(&new_admin);
+    client.cancel_admin_transfer();
+
+    assert_eq!(client.get_pending_admin(), None);
+    assert_eq!(client.try_accept_admin(), Err(Ok(Error::NoPendingAdmin)));
+    assert_eq!(client.get_admin(), admin);
+}
+
+#[test]
+fn test_admin_transfer_delay() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin) = setup(&env);
+    let new_admin = Address::generate(&env);
+
+    client.set_admin_transfer_delay(&86_400);
+    assert_eq!(client.get_admin_transfer_proposal(), None);
+
+    env.ledger().set_timestamp(1000);
+    client.propose_admin_transfer(&new_admin);
+    assert_eq!(
+        client.get_admin_transfer_proposal(),
+        Some((new_admin.clone(), 87_400))
+    );
+
+    // Too early: the oversight window is still open
+    env.ledger().set_timestamp(87_399);
+    assert_eq!(
+        client.try_accept_admin(),
+        Err(Ok(Error::TransferDelayNotElapsed))
+    );
+    assert_eq!(client.get_admin(), admin);
+
+    env.ledger().set_timestamp(87_400);
+    client.accept_admin();
+    assert_eq!(client.get_admin(), new_admin);
+    assert_eq!(client.get_admin_transfer_proposal(), None);
+}
+
+#[test]
+fn test_operator_management() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin) = setup(&env);
+    let operator = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_client, token_admin_client) = setup_token(&env, &token_admin);
+
+    token_admin_client.mint(&admin, &1000);
+    client.fund(&token_client.address, &admin, &1000);
+
+    // Not an operator yet
+    let res = client.try_create_package(
+        &operator,
+        &1,
+        &recipient,
+        &100,
+        &token_client.address,
+        &0,
+        &0,
+        &None,
+        &None,
+    );
+    assert_eq!(res, Err(Ok(Error::NotAuthorized)));
+
+    client.add_operator(&operator);
+    assert!(client.is_operator(&operator));
+    assert_eq!(client.get_operators().len(), 1);
+
+    client.create_package(
+        &operator,
+        &1,
+        &recipient,
+        &100,
+        &token_client.address,
+        &0,
+        &0,
+        &None,
+        &None,
+    );
+
+    client.remove_operator(&operator);
+    assert!(!client.is_operator(&operator));
+    assert_eq!(client.get_operators().len(), 0);
+
+    let res = client.try_create_package(
+        &operator,
+        &2,
+        &recipient,
+        &100,
+        &token_client.address,
+        &0,
+        &0,
+        &None,
+        &None,
+    );
+    assert_eq!(res, Err(Ok(Error::NotAuthorized)));
+}
+
+#[test]
+fn test_pause_blocks_mutations() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin) = setup(&env);
+    let recipient = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_client, token_admin_client) = setup_token(&env, &token_admin);
+
+    token_admin_client.mint(&admin, &1000);
+    client.fund(&token_client.address, &admin, &500);
+    client.create_package(
+        &admin,
+        &1,
+        &recipient,
+        &100,
+        &token_client.address,
+        &0,
+        &0,
+        &None,
+        &None,
+    );
+
+    client.pause();
+    assert!(client.is_paused());
+
+    assert_eq!(
+        client.try_fund(&token_client.address, &admin, &100),
+        Err(Ok(Error::ContractPaused))
+    );
+    assert_eq!(
+        client.try_create_package(
+            &admin,
+            &2,
+            &recipient,
+            &100,
+            &token_client.address,
+            &0,
+            &0,
+            &None,
+            &None
+        ),
+        Err(Ok(Error::ContractPaused))
+    );
+    assert_eq!(client.try_claim(&1), Err(Ok(Error::ContractPaused)));
+    assert_eq!(client.try_disburse(&1), Err(Ok(Error::ContractPaused)));
+    assert_eq!(client.try_revoke(&1), Err(Ok(Error::ContractPaused)));
+    assert_eq!(client.try_refund(&1), Err(Ok(Error::ContractPaused)));
+
+    client.unpause();
+    assert!(!client.is_paused());
+    client.claim(&1);
+    assert_eq!(token_client.balance(&recipient), 100);
+}
+
+#[test]
+fn test_multisig_proposals() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin) = setup(&env);
+    let recipient = Address::generate(&env);
+    let outsider = Address::generate(&env);
+    let signers = vec![
+        &env,
+        Address::generate(&env),
+        Address::generate(&env),
+        Address::generate(&env),
+    ];
+    let (s1, s2, s3) = (
+        signers.get(0).unwrap(),
+        signers.get(1).unwrap(),
+        signers.get(2).unwrap(),
+    );
+    let token_admin = Address::generate(&env);
+    let (token_client, token_admin_client) = setup_token(&env, &token_admin);
+    let token = token_client.address.clone();
+
+    token_admin_client.mint(&admin, &1000);
+    client.fund(&token, &admin, &1000);
+    client.create_package(&admin, &1, &recipient, &100, &token, &0, &0, &None, &None);
+
+    assert_eq!(
+        client.try_set_multisig(&signers, &4),
+        Err(Ok(Error::InvalidThreshold))
+    );
+    client.set_multisig(&signers, &2);
+    assert_eq!(client.get_multisig_threshold(), 2);
+
+    // Covered actions can no longer be called directly
+    assert_eq!(client.try_revoke(&1), Err(Ok(Error::MultisigRequired)));
+    assert_eq!(client.try_refund(&1), Err(Ok(Error::MultisigRequired)));
+    assert_eq!(
+        client.try_withdraw_excess(&token, &100),
+        Err(Ok(Error::MultisigRequired))
+    );
+    assert_eq!(
+        client.try_transfer_admin(&outsider),
+        Err(Ok(Error::MultisigRequired))
+    );
+
+    let revoke = ActionParams {
+        package_id: 1,
+        token: None,
+        amount: 0,
+        address: None,
+        wasm_hash: None,
+        version: None,
+        signers: vec![&env],
+        threshold: 0,
+    };
+    assert_eq!(
+        client.try_propose_action(&outsider, &AdminAction::Revoke, &revoke),
+        Err(Ok(Error::NotAuthorized))
+    );
+
+    // One approval is not enough
+    let proposal_id = client.propose_action(&s1, &AdminAction::Revoke, &revoke);
+    assert!(!client.get_proposal(&proposal_id).executed);
+    assert_eq!(client.get_package(&1).status, PackageStatus::Created);
+    assert_eq!(
+        client.try_approve_proposal(&s1, &proposal_id),
+        Err(Ok(Error::AlreadyApproved))
+    );
+
+    // The second approval executes the revocation
+    client.approve_proposal(&s2, &proposal_id);
+    assert!(client.get_proposal(&proposal_id).executed);
+    assert_eq!(client.get_package(&1).status, PackageStatus::Cancelled);
+    assert_eq!(
+        client.try_approve_proposal(&s3, &proposal_id),
+        Err(Ok(Error::InvalidState))
+    );
+
+    // Pool withdrawals go to the admin
+    let withdraw = ActionParams {
+        package_id: 0,
+        token: Some(token.clone()),
+        amount: 400,
+        address: None,
+        wasm_hash: None,
+        version: None,
+        signers: vec![&env],
+        threshold: 0,
+    };
+    let proposal_id = client.propose_action(&s3, &AdminAction::WithdrawExcess, &withdraw);
+    client.approve_proposal(&s1, &proposal_id);
+    assert_eq!(token_client.balance(&admin), 400);
+
+    assert_eq!(
+        client.try_approve_proposal(&s1, &99),
+        Err(Ok(Error::ProposalNotFound))
+    );
+
+    // Payouts to an alternate address need approval too
+    client.create_package(&admin, &2, &recipient, &100, &token, &0, &0, &None, &None);
+    assert_eq!(
+        client.try_disburse_to(&2, &outsider),
+        Err(Ok(Error::MultisigRequired))
+    );
+    let disburse_to = ActionParams {
+        package_id: 2,
+        token: None,
+        amount: 0,
+        address: Some(outsider.clone()),
+        wasm_hash: None,
+        version: None,
+        signers: vec![&env],
+        threshold: 0,
+    };
+    let proposal_id = client.propose_action(&s1, &AdminAction::DisburseTo, &disburse_to);
+    client.approve_proposal(&s3, &proposal_id);
+    assert_eq!(client.get_package(&2).status, PackageStatus::Disbursed);
+    assert_eq!(token_client.balance(&outsider), 100);
+
+    // The signer set itself can only change through a proposal
+    assert_eq!(
+        client.try_set_multisig(&vec![&env], &0),
+        Err(Ok(Error::MultisigRequired))
+    );
+    let disable = ActionParams {
+        package_id: 0,
+        token: None,
+        amount: 0,
+        address: None,
+        wasm_hash: None,
+        version: None,
+        signers: vec![&env],
+        threshold: 0,
+    };
+    let proposal_id = client.propose_action(&s2, &AdminAction::SetMultisig, &disable);
+    client.approve_proposal(&s3, &proposal_id);
+    assert_eq!(client.get_multisig_threshold(), 0);
+
+    // Turning multisig off restores direct admin control
+    client.refund(&1);
+    assert_eq!(token_client.balance(&admin), 500);
+}
+
+#[test]
+fn test_admin_log() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin) = setup(&env);
+    let operator = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_client, token_admin_client) = setup_token(&env, &token_admin);
+    let token = token_client.address.clone();
+
+    token_admin_client.mint(&admin, &1000);
+    client.fund(&token, &admin, &1000);
+    assert_eq!(client.get_admin_log_count(), 0);
+
+    client.add_operator(&operator);
+    client.create_package(
+        &operator, &1, &recipient, &100, &token, &0, &0, &None, &None,
+    );
+    client.revoke(&1);
+    client.refund(&1);
+    client.withdraw_excess(&token, &50);
+
+    // Failed calls leave no trace
+    assert!(client.try_revoke(&1).is_err());
+    assert_eq!(client.get_admin_log_count(), 5);
+
+    let log = client.get_admin_log(&0, &10);
+    assert_eq!(log.len(), 5);
+    let actions = [
+        symbol_short!("add_op"),
+        symbol_short!("create"),
+        symbol_short!("revoke"),
+        symbol_short!("refund"),
+        symbol_short!("withdraw"),
+    ];
+    for (entry, action) in log.iter().zip(actions) {
+        assert_eq!(entry.action, action);
+    }
+
+    let create = log.get(1).unwrap();
+    assert_eq!(create.actor, operator);
+    assert_eq!(create.package_id, Some(1));
+    assert_eq!(log.get(0).unwrap().actor, admin);
+    assert_eq!(log.get(4).unwrap().package_id, None);
+
+    // Pagination
+    let page = client.get_admin_log(&3, &10);
+    assert_eq!(page.len(), 2);
+    assert_eq!(page.get(0).unwrap().action, symbol_short!("refund"));
+    assert_eq!(client.get_admin_log(&1, &2).len(), 2);
+    assert_eq!(client.get_admin_log(&5, &10).len(), 0);
+
+    // Configuration and metadata changes are logged too
+    client.set_claim_grace_period(&3600);
+    client.set_package_note(&1, &String::from_str(&env, "checked"));
+    client.lock_metadata(&1);
+    assert_eq!(client.get_admin_log_count(), 8);
+    let tail = client.get_admin_log(&5, &10);
+    assert_eq!(tail.get(0).unwrap().action, symbol_short!("grace"));
+    assert_eq!(tail.get(0).unwrap().package_id, None);
+    assert_eq!(tail.get(1).unwrap().action, symbol_short!("note"));
+    assert_eq!(tail.get(2).unwrap().action, symbol_short!("meta_lock"));
+    assert_eq!(tail.get(2).unwrap().package_id, Some(1));
+}
+
+#[test]
+fn test_version() {
+    let env = Env::default();
+    let (client, _admin) = setup(&env);
+    assert_eq!(client.get_version(), String::from_str(&env, "1.0.0"));
+}
+
+#[test]
+fn test_upgrade_requires_admin() {
+    let env = Env::default();
+
+    let admin = Address::generate(&env);
+    let contract_id = env.register(AidEscrow, ());
+    let client = AidEscrowClient::new(&env, &contract_id);
+    client.init(&admin);
+
+    // No admin signature: the host rejects the call before any code swap
+    let wasm_hash = BytesN::from_array(&env, &[7; 32]);
+    assert!(client.try_upgrade(&wasm_hash, &None).is_err());
+}
+
+#[test]
+fn test_upgrade_goes_through_multisig() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin) = setup(&env);
+    let signer = Address::generate(&env);
+    client.set_multisig(&vec![&env, signer.clone()], &1);
+
+    let wasm_hash = BytesN::from_array(&env, &[7; 32]);
+    assert_eq!(
+        client.try_upgrade(&wasm_hash, &None),
+        Err(Ok(Error::MultisigRequired))
+    );
+
+    // A proposal without a hash cannot execute
+    let params = ActionParams {
+        package_id: 0,
+        token: None,
+        amount: 0,
+        address: None,
+        wasm_hash: None,
+        version: None,
+        signers: vec![&env],
+        threshold: 0,
+    };
+    assert_eq!(
+        client.try_propose_action(&signer, &AdminAction::Upgrade, &params),
+        Err(Ok(Error::InvalidState))
+    );
+}
+
+#[test]
+fn test_refund_destination_override() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin) = setup(&env);
+    let treasury = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_client, token_admin_client) = setup_token(&env, &token_admin);
+    let token = token_client.address.clone();
+
+    token_admin_client.mint(&admin, &1000);
+    client.fund(&token, &admin, &1000);
+    client.create_package(&admin, &1, &recipient, &100, &token, &0, &0, &None, &None);
+    client.create_package(&admin, &2, &recipient, &200, &token, &0, &0, &None, &None);
+
+    // Default: refunds go back to the admin
+    assert_eq!(client.get_refund_destination(), None);
+    client.revoke(&1);
+    client.refund(&1);
+    assert_eq!(token_client.balance(&admin), 100);
+
+    // Override is respected even though the admin triggers the refund
+    client.set_refund_destination(&treasury);
+    assert_eq!(client.get_refund_destination(), Some(treasury.clone()));
+    client.revoke(&2);
+    client.refund(&2);
+    assert_eq!(token_client.balance(&treasury), 200);
+    assert_eq!(token_client.balance(&admin), 100);
+}
+
+#[test]
+fn test_process_auto_refunds() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin) = setup(&env);
+    let recipient = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_client, token_admin_client) = setup_token(&env, &token_admin);
+    let (other_client, other_admin_client) = setup_token(&env, &token_admin);
+    let token = token_client.address.clone();
+
+    token_admin_client.mint(&admin, &1000);
+    other_admin_client.mint(&admin, &1000);
+    client.fund(&token, &admin, &1000);
+    client.fund(&other_client.address, &admin, &1000);
+
+    env.ledger().set_timestamp(1000);
+    client.create_package(
+        &admin, &1, &recipient, &100, &token, &1100, &0, &None, &None,
+    );
+    client.create_package(&admin, &2, &recipient, &200, &token, &0, &0, &None, &None);
+    client.create_package(&admin, &3, &recipient, &300, &token, &0, &0, &None, &None);
+    client.create_package(&admin, &4, &recipient, &50, &token, &0, &0, &None, &None);
+    client.create_package(
+        &admin,
+        &5,
+        &recipient,
+        &400,
+        &other_client.address,
+        &0,
+        &0,
+        &None,
+        &None,
+    );
+    client.revoke(&2);
+    client.revoke(&5);
+    client.claim(&4);
+    env.ledger().set_timestamp(1200);
+
+    let ids = vec![&env, 1, 2, 3, 4, 5, 99];
+    assert_eq!(
+        client.try_process_auto_refunds(&token, &ids),
+        Err(Ok(Error::AutoRefundDisabled))
+    );
+
+    client.set_auto_refund_enabled(&true);
+    assert!(client.is_auto_refund_enabled());
+
+    // Expired (1) and cancelled (2) are refunded; active, claimed, other-token and
+    // unknown IDs are skipped without aborting the run
+    assert_eq!(client.process_auto_refunds(&token, &ids), 2);
+    assert_eq!(client.get_package(&1).status, PackageStatus::Refunded);
+    assert_eq!(client.get_package(&2).status, PackageStatus::Refunded);
+    assert_eq!(client.get_package(&3).status, PackageStatus::Created);
+    assert_eq!(client.get_package(&5).status, PackageStatus::Cancelled);
+    assert_eq!(token_client.balance(&admin), 300);
+
+    // Nothing left to do on a second pass
+    assert_eq!(client.process_auto_refunds(&token, &ids), 0);
+}
+
+#[test]
+fn test_withdraw_excess_to() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin) = setup(&env);
+    let treasury = Address::generate(&env);
+    let outsider = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_client, token_admin_client) = setup_token(&env, &token_admin);
+    let token = token_client.address.clone();
+
+    token_admin_client.mint(&admin, &1000);
+    client.fund(&token, &admin, &1000);
+    client.create_package(&admin, &1, &recipient, &600, &token, &0, &0, &None, &None);
+
+    // Locked funds stay untouchable
+    assert_eq!(
+        client.try_withdraw_excess_to(&token, &401, &treasury),
+        Err(Ok(Error::InsufficientFunds))
+    );
+
+    client.withdraw_excess_to(&token, &300, &treasury);
+    assert_eq!(token_client.balance(&treasury), 300);
+    assert_eq!(token_client.balance(&admin), 0);
+    assert_eq!(client.get_available_balance(&token), 100);
+
+    // A non-admin signature is rejected, whatever the destination
+    for destination in [treasury.clone(), outsider.clone()] {
+        env.mock_auths(&[MockAuth {
+            address: &outsider,
+            invoke: &MockAuthInvoke {
+                contract: &client.address,
+                fn_name: "withdraw_excess_to",
+                args: (token.clone(), 100_i128, destination.clone()).into_val(&env),
+                sub_invokes: &[],
+            },
+        }]);
+        assert!(
+            client
+                .try_withdraw_excess_to(&token, &100, &destination)
+                .is_err()
+        );
+    }
+    assert_eq!(token_client.balance(&outsider), 0);
+    assert_eq!(client.get_available_balance(&token), 100);
+}
+
+#[test]
+fn test_init_twice_fails() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let other = Address::generate(&env);
+    let contract_id = env.register(AidEscrow, ());
+    let client = AidEscrowClient::new(&env, &contract_id);
+
+    client.init(&admin);
+    assert_eq!(client.try_init(&other), Err(Ok(Error::AlreadyInitialized)));
+    assert_eq!(client.get_admin(), admin);
+}
+
+#[test]
+fn test_get_admin_before_init_fails() {
+    let env = Env::default();
+    let contract_id = env.register(AidEscrow, ());
+    let client = AidEscrowClient::new(&env, &contract_id);
+
+    assert_eq!(client.try_get_admin(), Err(Ok(Error::NotInitialized)));
+}
+
+#[test]
+fn test_create_package_before_init_fails() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let caller = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_client, token_admin_client) = setup_token(&env, &token_admin);
+    let token = token_client.address.clone();
+
+    let contract_id = env.register(AidEscrow, ());
+    let client = AidEscrowClient::new(&env, &contract_id);
+    token_admin_client.mint(&contract_id, &1000);
+
+    assert_eq!(
+        client.try_create_package(&caller, &1, &recipient, &100, &token, &0, &0, &None, &None),
+        Err(Ok(Error::NotInitialized))
+    );
+    assert_eq!(client.get_total_locked(&token), 0);
+}
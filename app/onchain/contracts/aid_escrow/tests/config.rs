@@ -0,0 +1,841 @@
+#![cfg(test)]
+
+use aid_escrow::{AidEscrow, AidEscrowClient, Error, PackageStatus};
+use soroban_sdk::{
+    Address, BytesN, Env, String,
+    testutils::{Address as _, Ledger},
+    token::{StellarAssetClient, TokenClient},
+    vec,
+};
+
+fn setup_token(env: &Env, admin: &Address) -> (TokenClient<'static>, StellarAssetClient<'static>) {
+    let token_contract = env.register_stellar_asset_contract_v2(admin.clone());
+    let token_client = TokenClient::new(env, &token_contract.address());
+    let token_admin_client = StellarAssetClient::new(env, &token_contract.address());
+    (token_client, token_admin_client)
+}
+
+#[test]
+fn test_token_whitelist() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_client, token_admin_client) = setup_token(&env, &token_admin);
+
+    let contract_id = env.register(AidEscrow, ());
+    let client = AidEscrowClient::new(&env, &contract_id);
+    client.init(&admin);
+
+    token_admin_client.mint(&admin, &1000);
+
+    // Disabled by default: every token is allowed
+    assert!(client.is_token_allowed(&token_client.address));
+
+    client.set_token_whitelist_enabled(&true);
+    assert!(!client.is_token_allowed(&token_client.address));
+
+    let res = client.try_fund(&token_client.address, &admin, &500);
+    assert_eq!(res, Err(Ok(Error::TokenNotAllowed)));
+
+    client.add_allowed_token(&token_client.address);
+    assert!(client.is_token_allowed(&token_client.address));
+    client.fund(&token_client.address, &admin, &500);
+    client.create_package(
+        &admin,
+        &1,
+        &recipient,
+        &100,
+        &token_client.address,
+        &0,
+        &0,
+        &None,
+        &None,
+    );
+
+    client.remove_allowed_token(&token_client.address);
+    let res = client.try_create_package(
+        &admin,
+        &2,
+        &recipient,
+        &100,
+        &token_client.address,
+        &0,
+        &0,
+        &None,
+        &None,
+    );
+    assert_eq!(res, Err(Ok(Error::TokenNotAllowed)));
+}
+
+#[test]
+fn test_fee_deduction_and_collection() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_client, token_admin_client) = setup_token(&env, &token_admin);
+
+    let contract_id = env.register(AidEscrow, ());
+    let client = AidEscrowClient::new(&env, &contract_id);
+    client.init(&admin);
+
+    token_admin_client.mint(&admin, &2000);
+    client.fund(&token_client.address, &admin, &2000);
+
+    assert_eq!(
+        client.try_set_fee_rate_bps(&token_client.address, &1001),
+        Err(Ok(Error::InvalidFeeRate))
+    );
+    client.set_fee_rate_bps(&token_client.address, &250); // 2.5%
+
+    client.create_package(
+        &admin,
+        &1,
+        &recipient,
+        &1000,
+        &token_client.address,
+        &0,
+        &0,
+        &None,
+        &None,
+    );
+    client.claim(&1);
+
+    assert_eq!(token_client.balance(&recipient), 975);
+    assert_eq!(client.get_fees_accrued(&token_client.address), 25);
+
+    // Accrued fees stay earmarked: only 2000 - 1000 = 1000 is free
+    let res = client.try_create_package(
+        &admin,
+        &2,
+        &recipient,
+        &1001,
+        &token_client.address,
+        &0,
+        &0,
+        &None,
+        &None,
+    );
+    assert_eq!(res, Err(Ok(Error::InsufficientFunds)));
+
+    client.collect_fees(&token_client.address);
+    assert_eq!(token_client.balance(&admin), 25);
+    assert_eq!(client.get_fees_accrued(&token_client.address), 0);
+    assert_eq!(token_client.balance(&contract_id), 1000);
+
+    // After collection the remaining pool is fully available again
+    client.create_package(
+        &admin,
+        &2,
+        &recipient,
+        &1000,
+        &token_client.address,
+        &0,
+        &0,
+        &None,
+        &None,
+    );
+}
+
+#[test]
+fn test_default_expiry_duration() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_client, token_admin_client) = setup_token(&env, &token_admin);
+
+    let contract_id = env.register(AidEscrow, ());
+    let client = AidEscrowClient::new(&env, &contract_id);
+    client.init(&admin);
+
+    token_admin_client.mint(&admin, &1000);
+    client.fund(&token_client.address, &admin, &1000);
+
+    assert_eq!(client.get_default_expiry_duration(), None);
+    let res = client.try_create_package_default_expiry(
+        &admin,
+        &1,
+        &recipient,
+        &100,
+        &token_client.address,
+    );
+    assert_eq!(res, Err(Ok(Error::NoDefaultExpiry)));
+
+    let thirty_days = 30 * 86400;
+    client.set_default_expiry_duration(&thirty_days);
+    assert_eq!(client.get_default_expiry_duration(), Some(thirty_days));
+
+    env.ledger().set_timestamp(5000);
+    client.create_package_default_expiry(&admin, &1, &recipient, &100, &token_client.address);
+    assert_eq!(client.get_package(&1).expires_at, 5000 + thirty_days);
+}
+
+#[test]
+fn test_package_amount_limits() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_client, token_admin_client) = setup_token(&env, &token_admin);
+
+    let contract_id = env.register(AidEscrow, ());
+    let client = AidEscrowClient::new(&env, &contract_id);
+    client.init(&admin);
+
+    token_admin_client.mint(&admin, &10_000);
+    client.fund(&token_client.address, &admin, &10_000);
+
+    assert_eq!(client.get_token_limits(&token_client.address), (0, 0));
+
+    client.set_min_package_amount(&token_client.address, &100);
+    client.set_max_package_amount(&token_client.address, &1000);
+    assert_eq!(client.get_token_limits(&token_client.address), (100, 1000));
+
+    let res = client.try_create_package(
+        &admin,
+        &1,
+        &recipient,
+        &99,
+        &token_client.address,
+        &0,
+        &0,
+        &None,
+        &None,
+    );
+    assert_eq!(res, Err(Ok(Error::AmountBelowMinimum)));
+
+    let res = client.try_create_package(
+        &admin,
+        &1,
+        &recipient,
+        &1001,
+        &token_client.address,
+        &0,
+        &0,
+        &None,
+        &None,
+    );
+    assert_eq!(res, Err(Ok(Error::AmountAboveMaximum)));
+
+    // Bounds are inclusive
+    client.create_package(
+        &admin,
+        &1,
+        &recipient,
+        &100,
+        &token_client.address,
+        &0,
+        &0,
+        &None,
+        &None,
+    );
+    client.create_package(
+        &admin,
+        &2,
+        &recipient,
+        &1000,
+        &token_client.address,
+        &0,
+        &0,
+        &None,
+        &None,
+    );
+}
+
+#[test]
+fn test_recipient_allowlist() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let vetted = Address::generate(&env);
+    let unvetted = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_client, token_admin_client) = setup_token(&env, &token_admin);
+
+    let contract_id = env.register(AidEscrow, ());
+    let client = AidEscrowClient::new(&env, &contract_id);
+    client.init(&admin);
+
+    token_admin_client.mint(&admin, &1000);
+    client.fund(&token_client.address, &admin, &1000);
+
+    assert!(client.is_recipient_allowed(&unvetted));
+
+    client.set_allowlist_mode(&true);
+    client.add_recipient_to_allowlist(&vetted);
+    assert!(client.is_recipient_allowed(&vetted));
+    assert!(!client.is_recipient_allowed(&unvetted));
+
+    let res = client.try_create_package(
+        &admin,
+        &1,
+        &unvetted,
+        &100,
+        &token_client.address,
+        &0,
+        &0,
+        &None,
+        &None,
+    );
+    assert_eq!(res, Err(Ok(Error::RecipientNotAllowed)));
+
+    client.create_package(
+        &admin,
+        &1,
+        &vetted,
+        &100,
+        &token_client.address,
+        &0,
+        &0,
+        &None,
+        &None,
+    );
+
+    // Reassignment is gated the same way
+    assert_eq!(
+        client.try_reassign_recipient(&1, &unvetted),
+        Err(Ok(Error::RecipientNotAllowed))
+    );
+
+    client.remove_recipient_from_allowlist(&vetted);
+    let res = client.try_create_package(
+        &admin,
+        &2,
+        &vetted,
+        &100,
+        &token_client.address,
+        &0,
+        &0,
+        &None,
+        &None,
+    );
+    assert_eq!(res, Err(Ok(Error::RecipientNotAllowed)));
+
+    client.set_allowlist_mode(&false);
+    client.create_package(
+        &admin,
+        &2,
+        &unvetted,
+        &100,
+        &token_client.address,
+        &0,
+        &0,
+        &None,
+        &None,
+    );
+}
+
+#[test]
+fn test_recipient_blacklist() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_client, token_admin_client) = setup_token(&env, &token_admin);
+
+    let contract_id = env.register(AidEscrow, ());
+    let client = AidEscrowClient::new(&env, &contract_id);
+    client.init(&admin);
+
+    token_admin_client.mint(&admin, &1000);
+    client.fund(&token_client.address, &admin, &1000);
+    client.create_package(
+        &admin,
+        &1,
+        &recipient,
+        &100,
+        &token_client.address,
+        &0,
+        &0,
+        &None,
+        &None,
+    );
+
+    // Fraud detected after creation
+    client.blacklist_recipient(&recipient);
+    assert!(client.is_blacklisted(&recipient));
+
+    assert_eq!(client.try_claim(&1), Err(Ok(Error::RecipientBlacklisted)));
+    let res = client.try_create_package(
+        &admin,
+        &2,
+        &recipient,
+        &100,
+        &token_client.address,
+        &0,
+        &0,
+        &None,
+        &None,
+    );
+    assert_eq!(res, Err(Ok(Error::RecipientBlacklisted)));
+
+    client.unblacklist_recipient(&recipient);
+    assert!(!client.is_blacklisted(&recipient));
+    client.claim(&1);
+    assert_eq!(token_client.balance(&recipient), 100);
+}
+
+#[test]
+fn test_get_config() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let token = Address::generate(&env);
+
+    let contract_id = env.register(AidEscrow, ());
+    let client = AidEscrowClient::new(&env, &contract_id);
+    assert_eq!(client.try_get_config(), Err(Ok(Error::NotInitialized)));
+    client.init(&admin);
+
+    let config = client.get_config();
+    assert_eq!(config.admin, admin);
+    assert!(!config.paused);
+    assert!(!config.token_whitelist_enabled);
+    assert_eq!(config.default_expiry_duration, None);
+    assert!(!config.allowlist_mode);
+    assert_eq!(config.fee_rates.len(), 0);
+
+    client.pause();
+    client.set_token_whitelist_enabled(&true);
+    client.set_default_expiry_duration(&3600);
+    client.set_allowlist_mode(&true);
+    client.set_fee_rate_bps(&token, &250);
+
+    let config = client.get_config();
+    assert!(config.paused);
+    assert!(config.token_whitelist_enabled);
+    assert_eq!(config.default_expiry_duration, Some(3600));
+    assert!(config.allowlist_mode);
+    assert_eq!(config.fee_rates.get(token), Some(250));
+}
+
+#[test]
+fn test_max_packages_per_recipient() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let other = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_client, token_admin_client) = setup_token(&env, &token_admin);
+    let token = token_client.address.clone();
+
+    let contract_id = env.register(AidEscrow, ());
+    let client = AidEscrowClient::new(&env, &contract_id);
+    client.init(&admin);
+
+    token_admin_client.mint(&admin, &1000);
+    client.fund(&token, &admin, &1000);
+
+    client.set_max_packages_per_recipient(&2);
+    client.create_package(&admin, &1, &recipient, &100, &token, &0, &0, &None, &None);
+    client.create_package(&admin, &2, &recipient, &100, &token, &0, &0, &None, &None);
+    assert_eq!(client.get_recipient_active_pkg_count(&recipient), 2);
+
+    assert_eq!(
+        client.try_create_package(&admin, &3, &recipient, &100, &token, &0, &0, &None, &None),
+        Err(Ok(Error::RecipientPackageLimitExceeded))
+    );
+
+    // Partial claims keep the package active
+    client.partial_claim(&1, &50);
+    assert_eq!(client.get_recipient_active_pkg_count(&recipient), 2);
+
+    // Terminal transitions free a slot
+    client.claim(&1);
+    assert_eq!(client.get_recipient_active_pkg_count(&recipient), 1);
+    client.create_package(&admin, &3, &recipient, &100, &token, &0, &0, &None, &None);
+
+    // Reassignment moves the package between counters and respects the cap
+    client.create_package(&admin, &4, &other, &100, &token, &0, &0, &None, &None);
+    assert_eq!(
+        client.try_reassign_recipient(&4, &recipient),
+        Err(Ok(Error::RecipientPackageLimitExceeded))
+    );
+    client.reassign_recipient(&3, &other);
+    assert_eq!(client.get_recipient_active_pkg_count(&recipient), 1);
+    assert_eq!(client.get_recipient_active_pkg_count(&other), 2);
+
+    // 0 removes the cap
+    client.set_max_packages_per_recipient(&0);
+    client.create_package(&admin, &5, &other, &100, &token, &0, &0, &None, &None);
+    assert_eq!(client.get_recipient_active_pkg_count(&other), 3);
+}
+
+#[test]
+fn test_expiry_duration_bounds() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_client, token_admin_client) = setup_token(&env, &token_admin);
+    let token = token_client.address.clone();
+
+    let contract_id = env.register(AidEscrow, ());
+    let client = AidEscrowClient::new(&env, &contract_id);
+    client.init(&admin);
+
+    token_admin_client.mint(&admin, &1000);
+    client.fund(&token, &admin, &1000);
+    env.ledger().set_timestamp(1000);
+
+    assert_eq!(client.get_expiry_bounds(), (0, 0));
+    client.set_min_expiry_duration(&3600);
+    client.set_max_expiry_duration(&86_400);
+    assert_eq!(client.get_expiry_bounds(), (3600, 86_400));
+
+    assert_eq!(
+        client.try_create_package(
+            &admin, &1, &recipient, &100, &token, &1001, &0, &None, &None
+        ),
+        Err(Ok(Error::ExpiryTooShort))
+    );
+    assert_eq!(
+        client.try_create_package(
+            &admin, &1, &recipient, &100, &token, &90_000, &0, &None, &None
+        ),
+        Err(Ok(Error::ExpiryTooLong))
+    );
+
+    // Bounds are inclusive, and packages without expiry are exempt
+    client.create_package(
+        &admin, &1, &recipient, &100, &token, &4600, &0, &None, &None,
+    );
+    client.create_package(
+        &admin, &2, &recipient, &100, &token, &87_400, &0, &None, &None,
+    );
+    client.create_package(&admin, &3, &recipient, &100, &token, &0, &0, &None, &None);
+
+    // Derived packages are checked too: a clone of package 1 would now live too briefly
+    env.ledger().set_timestamp(2000);
+    assert_eq!(
+        client.try_clone_package(&1, &4, &recipient, &50),
+        Err(Ok(Error::ExpiryTooShort))
+    );
+}
+
+#[test]
+fn test_rate_limit_window() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_client, token_admin_client) = setup_token(&env, &token_admin);
+    let token = token_client.address.clone();
+
+    let contract_id = env.register(AidEscrow, ());
+    let client = AidEscrowClient::new(&env, &contract_id);
+    client.init(&admin);
+
+    token_admin_client.mint(&admin, &1000);
+    client.fund(&token, &admin, &1000);
+
+    env.ledger().set_sequence_number(100);
+    client.set_rate_limit(&10, &2);
+    assert_eq!(client.get_rate_limit_state(), (10, 2, 0));
+
+    client.create_package(&admin, &1, &recipient, &10, &token, &0, &0, &None, &None);
+    client.create_package(&admin, &2, &recipient, &10, &token, &0, &0, &None, &None);
+    assert_eq!(client.get_rate_limit_state(), (10, 2, 2));
+    assert_eq!(
+        client.try_create_package(&admin, &3, &recipient, &10, &token, &0, &0, &None, &None),
+        Err(Ok(Error::RateLimitExceeded))
+    );
+
+    // Still inside the window on its last ledger
+    env.ledger().set_sequence_number(109);
+    assert_eq!(
+        client.try_create_package(&admin, &3, &recipient, &10, &token, &0, &0, &None, &None),
+        Err(Ok(Error::RateLimitExceeded))
+    );
+
+    // Every creation path shares the window
+    assert_eq!(
+        client.try_clone_package(&1, &3, &recipient, &10),
+        Err(Ok(Error::RateLimitExceeded))
+    );
+    let milestones = vec![&env, (10i128, String::from_str(&env, "stage"))];
+    assert_eq!(
+        client.try_create_package_with_milestones(&3, &recipient, &token, &milestones, &0),
+        Err(Ok(Error::RateLimitExceeded))
+    );
+
+    // Window elapsed: the counter resets
+    env.ledger().set_sequence_number(110);
+    assert_eq!(client.get_rate_limit_state(), (10, 2, 0));
+    client.create_package(&admin, &3, &recipient, &10, &token, &0, &0, &None, &None);
+    assert_eq!(client.get_rate_limit_state(), (10, 2, 1));
+
+    // Disabling the limit lifts the cap
+    client.set_rate_limit(&0, &0);
+    for id in 4..8u64 {
+        client.create_package(&admin, &id, &recipient, &10, &token, &0, &0, &None, &None);
+    }
+}
+
+#[test]
+fn test_auto_increment_package_ids() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let operator = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_client, token_admin_client) = setup_token(&env, &token_admin);
+    let token = token_client.address.clone();
+
+    let contract_id = env.register(AidEscrow, ());
+    let client = AidEscrowClient::new(&env, &contract_id);
+    client.init(&admin);
+    client.add_operator(&operator);
+
+    token_admin_client.mint(&admin, &1000);
+    client.fund(&token, &admin, &1000);
+
+    // Disabled by default: caller-chosen IDs are used as-is
+    assert!(!client.is_auto_increment_enabled());
+    assert_eq!(
+        client.create_package(&admin, &2, &recipient, &10, &token, &0, &0, &None, &None),
+        2
+    );
+
+    client.set_auto_increment_enabled(&true);
+    assert_eq!(client.get_next_package_id(), 1);
+    assert_eq!(
+        client.try_create_package(&admin, &7, &recipient, &10, &token, &0, &0, &None, &None),
+        Err(Ok(Error::AutoIncrementEnabled))
+    );
+
+    // Interleaved creators draw from the same counter; the manual ID 2 is skipped
+    let creators = [&admin, &operator, &operator, &admin];
+    let mut last = 0;
+    for creator in creators {
+        let id = client.create_package(creator, &0, &recipient, &10, &token, &0, &0, &None, &None);
+        assert!(id > last);
+        assert_eq!(client.get_package(&id).id, id);
+        last = id;
+    }
+    assert_eq!(last, 5);
+    assert_eq!(client.get_next_package_id(), 6);
+
+    // The combined and idempotent creators follow the same rule
+    token_admin_client.mint(&admin, &10);
+    assert_eq!(
+        client.try_create_package_and_fund(&7, &recipient, &10, &token, &admin, &0),
+        Err(Ok(Error::AutoIncrementEnabled))
+    );
+    assert_eq!(
+        client.create_package_and_fund(&0, &recipient, &10, &token, &admin, &0),
+        6
+    );
+    let nonce = BytesN::from_array(&env, &[1; 32]);
+    assert_eq!(
+        client.try_create_package_idempotent(&nonce, &7, &recipient, &10, &token, &0),
+        Err(Ok(Error::AutoIncrementEnabled))
+    );
+    assert_eq!(
+        client.create_package_idempotent(&nonce, &0, &recipient, &10, &token, &0),
+        7
+    );
+    assert_eq!(
+        client.create_package_idempotent(&nonce, &0, &recipient, &10, &token, &0),
+        7
+    );
+    assert_eq!(client.get_next_package_id(), 8);
+
+    client.set_auto_increment_enabled(&false);
+    assert_eq!(
+        client.create_package(&admin, &100, &recipient, &10, &token, &0, &0, &None, &None),
+        100
+    );
+}
+
+#[test]
+fn test_error_description_covers_every_variant() {
+    let env = Env::default();
+    let contract_id = env.register(AidEscrow, ());
+    let client = AidEscrowClient::new(&env, &contract_id);
+
+    // Codes are contiguous from 1, so an unmapped code marks the end of the enum
+    let mut seen = Vec::new();
+    let mut code = 1;
+    while let Ok(err) = Error::try_from(soroban_sdk::Error::from_contract_error(code)) {
+        let description = client.error_description(&err);
+        assert!(!description.is_empty());
+        assert!(!seen.contains(&description));
+        seen.push(description);
+        code += 1;
+    }
+    assert_eq!(code, 46);
+    assert_eq!(
+        client.error_description(&Error::PackageExpired),
+        String::from_str(&env, "Package has passed its expiry timestamp")
+    );
+}
+
+#[test]
+fn test_kyc_gated_claim() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let attestor = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_client, token_admin_client) = setup_token(&env, &token_admin);
+    let token = token_client.address.clone();
+
+    let contract_id = env.register(AidEscrow, ());
+    let client = AidEscrowClient::new(&env, &contract_id);
+    client.init(&admin);
+
+    token_admin_client.mint(&admin, &1000);
+    client.fund(&token, &admin, &1000);
+    client.create_package(&admin, &1, &alice, &100, &token, &0, &0, &None, &None);
+    client.create_package(&admin, &2, &bob, &100, &token, &0, &0, &None, &None);
+    client.create_package(&admin, &3, &bob, &100, &token, &0, &0, &None, &None);
+
+    // No attestor configured yet
+    assert_eq!(
+        client.try_attest_recipient(&alice),
+        Err(Ok(Error::NotAuthorized))
+    );
+    client.set_kyc_attestor(&attestor);
+    assert_eq!(client.get_kyc_attestor(), Some(attestor.clone()));
+
+    client.set_package_require_kyc(&1, &true);
+    client.set_package_require_kyc(&2, &true);
+    assert!(client.package_requires_kyc(&1));
+    assert!(!client.package_requires_kyc(&3));
+
+    assert_eq!(client.try_claim(&1), Err(Ok(Error::KycRequired)));
+    assert_eq!(client.try_claim(&2), Err(Ok(Error::KycRequired)));
+    assert!(!client.is_package_claimable(&1));
+
+    client.attest_recipient(&alice);
+    assert!(client.is_kyc_attested(&alice));
+    assert!(client.is_package_claimable(&1));
+    assert!(!client.is_kyc_attested(&bob));
+    client.claim(&1);
+    assert_eq!(token_client.balance(&alice), 100);
+
+    // Packages without the flag are unaffected, and the flag can be lifted
+    client.claim(&3);
+    assert_eq!(client.try_claim(&2), Err(Ok(Error::KycRequired)));
+    client.set_package_require_kyc(&2, &false);
+    client.claim(&2);
+    assert_eq!(token_client.balance(&bob), 200);
+
+    // A withdrawn attestation gates claims again
+    client.create_package(&admin, &4, &alice, &100, &token, &0, &0, &None, &None);
+    client.set_package_require_kyc(&4, &true);
+    client.revoke_kyc_attestation(&alice);
+    assert!(!client.is_kyc_attested(&alice));
+    assert_eq!(client.try_claim(&4), Err(Ok(Error::KycRequired)));
+    client.attest_recipient(&alice);
+    client.claim(&4);
+    assert_eq!(token_client.balance(&alice), 200);
+}
+
+#[test]
+fn test_global_expiry_override() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_client, token_admin_client) = setup_token(&env, &token_admin);
+    let token = token_client.address.clone();
+
+    let contract_id = env.register(AidEscrow, ());
+    let client = AidEscrowClient::new(&env, &contract_id);
+    client.init(&admin);
+
+    token_admin_client.mint(&admin, &1000);
+    client.fund(&token, &admin, &1000);
+
+    env.ledger().set_timestamp(1000);
+    // 1: never expires, 2: expires before the override, 3: expires after it
+    client.create_package(&admin, &1, &recipient, &100, &token, &0, &0, &None, &None);
+    client.create_package(
+        &admin, &2, &recipient, &100, &token, &2000, &0, &None, &None,
+    );
+    client.create_package(
+        &admin, &3, &recipient, &100, &token, &9000, &0, &None, &None,
+    );
+    client.create_package(
+        &admin, &4, &recipient, &100, &token, &9000, &0, &None, &None,
+    );
+
+    // No override: each package keeps its own expiry
+    assert_eq!(client.get_global_expiry(), 0);
+    assert_eq!(client.get_effective_expiry(&1), 0);
+    assert_eq!(client.get_effective_expiry(&2), 2000);
+    assert_eq!(client.get_effective_expiry(&3), 9000);
+    assert_eq!(
+        client.get_packages_expiring_within(&token, &4500),
+        vec![&env, 2]
+    );
+
+    assert_eq!(
+        client.try_set_global_expiry_override(&1000),
+        Err(Ok(Error::InvalidExpiry))
+    );
+    client.set_global_expiry_override(&5000);
+    assert_eq!(client.get_effective_expiry(&1), 0);
+    assert_eq!(client.get_effective_expiry(&2), 2000);
+    assert_eq!(client.get_effective_expiry(&3), 5000);
+    assert_eq!(client.get_claim_deadline(&3), 5000);
+    assert_eq!(
+        client.get_packages_expiring_within(&token, &4500),
+        vec![&env, 2, 3, 4]
+    );
+    assert_eq!(
+        client.get_packages_expiring_within(&token, &3000),
+        vec![&env, 2]
+    );
+    assert_eq!(
+        client.try_force_expire(&3),
+        Err(Ok(Error::PackageNotExpired))
+    );
+
+    // Past the override: packages with an expiry are cut off, the no-expiry one is not
+    env.ledger().set_timestamp(5001);
+    assert_eq!(client.try_claim(&3), Err(Ok(Error::PackageExpired)));
+    assert_eq!(client.try_claim(&2), Err(Ok(Error::PackageExpired)));
+    client.claim(&1);
+
+    // Refunds and force-expiry follow the override as well
+    client.force_expire(&3);
+    assert_eq!(client.get_package(&3).status, PackageStatus::Expired);
+    client.refund(&2);
+    assert_eq!(client.get_package(&2).status, PackageStatus::Refunded);
+
+    // Clearing the override restores the package's own expiry
+    client.clear_global_expiry();
+    assert_eq!(client.get_effective_expiry(&4), 9000);
+    client.claim(&4);
+    assert_eq!(token_client.balance(&recipient), 200);
+}
@@ -1,10 +1,14 @@
 #![cfg(test)]
 
-use aid_escrow::{AidEscrow, AidEscrowClient, Error, PackageStatus};
+use aid_escrow::{
+    AidEscrow, AidEscrowClient, ClaimSimulation, ContractStats, Error, PackageStatus,
+    SolvencyStatus, StatusTransition, TokenBreakdown,
+};
 use soroban_sdk::{
-    Address, Env,
-    testutils::{Address as _, Ledger},
+    Address, Env, IntoVal, Map, String, Symbol, TryFromVal, Val, symbol_short,
+    testutils::{Address as _, Events, Ledger, MockAuth, MockAuthInvoke},
     token::{StellarAssetClient, TokenClient},
+    vec,
 };
 
 fn setup_token(env: &Env, admin: &Address) -> (TokenClient<'static>, StellarAssetClient<'static>) {
@@ -14,6 +18,53 @@ fn setup_token(env: &Env, admin: &Address) -> (TokenClient<'static>, StellarAsse
     (token_client, token_admin_client)
 }
 
+/// Checks the status counters against the actual status of every package in `ids`,
+/// including the derived invariant `active = total - terminal`.
+fn assert_status_counters(client: &AidEscrowClient, ids: &[u64]) {
+    let (mut active, mut claimed, mut disbursed) = (0, 0, 0);
+    let (mut cancelled, mut expired, mut refunded, mut transferred) = (0, 0, 0, 0);
+    for id in ids {
+        match client.get_package(id).status {
+            PackageStatus::Created | PackageStatus::PartiallyClaimed => active += 1,
+            PackageStatus::Claimed => claimed += 1,
+            PackageStatus::Disbursed => disbursed += 1,
+            PackageStatus::Cancelled => cancelled += 1,
+            PackageStatus::Expired => expired += 1,
+            PackageStatus::Refunded => refunded += 1,
+            PackageStatus::Transferred => transferred += 1,
+        }
+    }
+
+    assert_eq!(client.get_package_count(), ids.len() as u64);
+    assert_eq!(client.get_claimed_count(), claimed);
+    assert_eq!(client.get_disbursed_count(), disbursed);
+    assert_eq!(client.get_cancelled_count(), cancelled);
+    assert_eq!(client.get_expired_count(), expired);
+    assert_eq!(client.get_refunded_count(), refunded);
+    assert_eq!(client.get_transferred_count(), transferred);
+
+    let terminal = client.get_claimed_count()
+        + client.get_disbursed_count()
+        + client.get_cancelled_count()
+        + client.get_expired_count()
+        + client.get_refunded_count()
+        + client.get_transferred_count();
+    assert_eq!(client.get_package_count() - terminal, active);
+}
+
+/// Total locked for `token` must equal what the active packages in `ids` still hold
+/// (for untouched `Created` packages that is their full `amount`).
+fn assert_locked_invariant(client: &AidEscrowClient, token: &Address, ids: &[u64]) {
+    let mut expected: i128 = 0;
+    for id in ids {
+        let package = client.get_package(id);
+        if package.token == *token && package.status.is_active() {
+            expected += package.remaining_amount;
+        }
+    }
+    assert_eq!(client.get_total_locked(token), expected);
+}
+
 #[test]
 fn test_core_flow_fund_create_claim() {
     let env = Env::default();
@@ -37,25 +88,39 @@ fn test_core_flow_fund_create_claim() {
     // 2. Fund the contract (Pool)
     client.fund(&token_client.address, &admin, &5000);
     assert_eq!(token_client.balance(&contract_id), 5000);
+    assert_locked_invariant(&client, &token_client.address, &[]);
 
     // 3. Create Package
     let pkg_id = 101;
     let expiry = env.ledger().timestamp() + 86400; // 1 day later
-    client.create_package(&pkg_id, &recipient, &1000, &token_client.address, &expiry);
+    client.create_package(
+        &admin,
+        &pkg_id,
+        &recipient,
+        &1000,
+        &token_client.address,
+        &expiry,
+        &0,
+        &None,
+        &None,
+    );
 
     // Check Package State
     let pkg = client.get_package(&pkg_id);
     assert_eq!(pkg.status, PackageStatus::Created);
     assert_eq!(pkg.amount, 1000);
+    assert_locked_invariant(&client, &token_client.address, &[pkg_id]);
 
     // 4. Claim
     client.claim(&pkg_id);
+    assert_locked_invariant(&client, &token_client.address, &[pkg_id]);
 
     // Check Final State
     let pkg_claimed = client.get_package(&pkg_id);
     assert_eq!(pkg_claimed.status, PackageStatus::Claimed);
     assert_eq!(token_client.balance(&recipient), 1000);
     assert_eq!(token_client.balance(&contract_id), 4000); // 5000 - 1000
+    assert_status_counters(&client, &[pkg_id]);
 }
 
 #[test]
@@ -76,15 +141,51 @@ fn test_solvency_check() {
     client.fund(&token_client.address, &admin, &1000);
 
     // Try creating package > available balance
-    let res = client.try_create_package(&1, &recipient, &2000, &token_client.address, &0);
+    let res = client.try_create_package(
+        &admin,
+        &1,
+        &recipient,
+        &2000,
+        &token_client.address,
+        &0,
+        &0,
+        &None,
+        &None,
+    );
     assert_eq!(res, Err(Ok(Error::InsufficientFunds)));
 
+    assert_eq!(client.get_total_locked(&token_client.address), 0);
+    assert_eq!(client.get_available_balance(&token_client.address), 1000);
+
     // Create valid package using all funds
-    client.create_package(&2, &recipient, &1000, &token_client.address, &0);
+    client.create_package(
+        &admin,
+        &2,
+        &recipient,
+        &1000,
+        &token_client.address,
+        &0,
+        &0,
+        &None,
+        &None,
+    );
+    assert_eq!(client.get_total_locked(&token_client.address), 1000);
+    assert_eq!(client.get_available_balance(&token_client.address), 0);
 
     // Try creating another package (funds are locked)
-    let res2 = client.try_create_package(&3, &recipient, &1, &token_client.address, &0);
+    let res2 = client.try_create_package(
+        &admin,
+        &3,
+        &recipient,
+        &1,
+        &token_client.address,
+        &0,
+        &0,
+        &None,
+        &None,
+    );
     assert_eq!(res2, Err(Ok(Error::InsufficientFunds)));
+    assert_status_counters(&client, &[2]);
 }
 
 #[test]
@@ -109,7 +210,19 @@ fn test_expiry_and_refund() {
     env.ledger().set_timestamp(start_time);
     let pkg_id = 1;
     let expiry = start_time + 100;
-    client.create_package(&pkg_id, &recipient, &500, &token_client.address, &expiry);
+    client.create_package(
+        &admin,
+        &pkg_id,
+        &recipient,
+        &500,
+        &token_client.address,
+        &expiry,
+        &0,
+        &None,
+        &None,
+    );
+
+    assert_locked_invariant(&client, &token_client.address, &[pkg_id]);
 
     // Advance time past expiry
     env.ledger().set_timestamp(expiry + 1);
@@ -117,6 +230,7 @@ fn test_expiry_and_refund() {
     // Recipient tries to claim -> Should Fail
     let claim_res = client.try_claim(&pkg_id);
     assert_eq!(claim_res, Err(Ok(Error::PackageExpired)));
+    assert_locked_invariant(&client, &token_client.address, &[pkg_id]);
 
     // Admin refunds
     // Balance before refund: Admin has 0 (minted 1000, funded 1000)
@@ -129,6 +243,8 @@ fn test_expiry_and_refund() {
 
     let pkg = client.get_package(&pkg_id);
     assert_eq!(pkg.status, PackageStatus::Refunded);
+    assert_status_counters(&client, &[pkg_id]);
+    assert_locked_invariant(&client, &token_client.address, &[pkg_id]);
 }
 
 #[test]
@@ -149,17 +265,2112 @@ fn test_revoke_flow() {
     client.fund(&token_client.address, &admin, &1000);
 
     let pkg_id = 1;
-    client.create_package(&pkg_id, &recipient, &500, &token_client.address, &0);
+    client.create_package(
+        &admin,
+        &pkg_id,
+        &recipient,
+        &500,
+        &token_client.address,
+        &0,
+        &0,
+        &None,
+        &None,
+    );
+
+    assert_locked_invariant(&client, &token_client.address, &[pkg_id]);
 
     // Revoke
     client.revoke(&pkg_id);
 
     let pkg = client.get_package(&pkg_id);
     assert_eq!(pkg.status, PackageStatus::Cancelled);
+    assert_status_counters(&client, &[pkg_id]);
+    assert_locked_invariant(&client, &token_client.address, &[pkg_id]);
 
     // Funds are now unlocked. We can create a new package using those same funds.
     // If they were still locked, this would fail (Balance 1000, Used 500. Available 500. Request 1000 -> Fail).
     // Since revoked, Available should be 1000 again.
     let pkg_id_2 = 2;
-    client.create_package(&pkg_id_2, &recipient, &1000, &token_client.address, &0);
+    client.create_package(
+        &admin,
+        &pkg_id_2,
+        &recipient,
+        &1000,
+        &token_client.address,
+        &0,
+        &0,
+        &None,
+        &None,
+    );
+    assert_status_counters(&client, &[pkg_id, pkg_id_2]);
+    assert_locked_invariant(&client, &token_client.address, &[pkg_id, pkg_id_2]);
+}
+
+#[test]
+fn test_partial_claim_flow() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_client, token_admin_client) = setup_token(&env, &token_admin);
+
+    let contract_id = env.register(AidEscrow, ());
+    let client = AidEscrowClient::new(&env, &contract_id);
+    client.init(&admin);
+
+    token_admin_client.mint(&admin, &1000);
+    client.fund(&token_client.address, &admin, &1000);
+
+    let pkg_id = 1;
+    client.create_package(
+        &admin,
+        &pkg_id,
+        &recipient,
+        &1000,
+        &token_client.address,
+        &0,
+        &0,
+        &None,
+        &None,
+    );
+
+    // Cannot withdraw more than what is left
+    let res = client.try_partial_claim(&pkg_id, &1001);
+    assert_eq!(res, Err(Ok(Error::InvalidAmount)));
+
+    // First installment
+    client.partial_claim(&pkg_id, &300);
+    let pkg = client.get_package(&pkg_id);
+    assert_eq!(pkg.status, PackageStatus::PartiallyClaimed);
+    assert_eq!(pkg.remaining_amount, 700);
+    assert_eq!(token_client.balance(&recipient), 300);
+    assert_locked_invariant(&client, &token_client.address, &[pkg_id]);
+
+    // Only the withdrawn portion was unlocked: 700 is still earmarked
+    let res = client.try_create_package(
+        &admin,
+        &2,
+        &recipient,
+        &1,
+        &token_client.address,
+        &0,
+        &0,
+        &None,
+        &None,
+    );
+    assert_eq!(res, Err(Ok(Error::InsufficientFunds)));
+
+    // `claim` takes the rest
+    client.claim(&pkg_id);
+    let pkg = client.get_package(&pkg_id);
+    assert_eq!(pkg.status, PackageStatus::Claimed);
+    assert_eq!(pkg.remaining_amount, 0);
+    assert_eq!(pkg.amount, 1000);
+    assert_eq!(token_client.balance(&recipient), 1000);
+
+    let res = client.try_partial_claim(&pkg_id, &1);
+    assert_eq!(res, Err(Ok(Error::PackageNotActive)));
+    assert_status_counters(&client, &[pkg_id]);
+    assert_locked_invariant(&client, &token_client.address, &[pkg_id]);
+}
+
+#[test]
+fn test_delegated_claim() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let delegate = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_client, token_admin_client) = setup_token(&env, &token_admin);
+
+    let contract_id = env.register(AidEscrow, ());
+    let client = AidEscrowClient::new(&env, &contract_id);
+    client.init(&admin);
+
+    token_admin_client.mint(&admin, &1000);
+    client.fund(&token_client.address, &admin, &1000);
+    client.create_package(
+        &admin,
+        &1,
+        &recipient,
+        &400,
+        &token_client.address,
+        &0,
+        &0,
+        &None,
+        &None,
+    );
+    client.create_package(
+        &admin,
+        &2,
+        &recipient,
+        &400,
+        &token_client.address,
+        &0,
+        &0,
+        &None,
+        &None,
+    );
+
+    // No delegate registered
+    assert_eq!(
+        client.try_claim_as_delegate(&1),
+        Err(Ok(Error::NotAuthorized))
+    );
+
+    client.delegate_claim(&1, &delegate);
+    assert_eq!(client.get_claim_delegate(&1), Some(delegate.clone()));
+
+    // Delegate triggers the claim, but the recipient receives the funds
+    client.claim_as_delegate(&1);
+    assert_eq!(token_client.balance(&recipient), 400);
+    assert_eq!(token_client.balance(&delegate), 0);
+    assert_eq!(client.get_package(&1).status, PackageStatus::Claimed);
+
+    // Revoked delegation can no longer be used
+    client.delegate_claim(&2, &delegate);
+    client.revoke_claim_delegate(&2);
+    assert_eq!(client.get_claim_delegate(&2), None);
+    assert_eq!(
+        client.try_claim_as_delegate(&2),
+        Err(Ok(Error::NotAuthorized))
+    );
+}
+
+#[test]
+fn test_withdraw_excess() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_client, token_admin_client) = setup_token(&env, &token_admin);
+
+    let contract_id = env.register(AidEscrow, ());
+    let client = AidEscrowClient::new(&env, &contract_id);
+    client.init(&admin);
+
+    token_admin_client.mint(&admin, &1000);
+    client.fund(&token_client.address, &admin, &1000);
+    client.create_package(
+        &admin,
+        &1,
+        &recipient,
+        &600,
+        &token_client.address,
+        &0,
+        &0,
+        &None,
+        &None,
+    );
+
+    // Cannot dip into the 600 locked for package 1
+    let res = client.try_withdraw_excess(&token_client.address, &401);
+    assert_eq!(res, Err(Ok(Error::InsufficientFunds)));
+
+    client.withdraw_excess(&token_client.address, &400);
+    assert_eq!(token_client.balance(&admin), 400);
+    assert_eq!(token_client.balance(&contract_id), 600);
+    assert_eq!(client.get_total_locked(&token_client.address), 600);
+
+    // Package is still fully claimable
+    client.claim(&1);
+    assert_eq!(token_client.balance(&recipient), 600);
+}
+
+#[test]
+fn test_withdraw_excess_keeps_pending_refunds() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_client, token_admin_client) = setup_token(&env, &token_admin);
+    let token = token_client.address.clone();
+
+    let contract_id = env.register(AidEscrow, ());
+    let client = AidEscrowClient::new(&env, &contract_id);
+    client.init(&admin);
+
+    token_admin_client.mint(&admin, &1000);
+    client.fund(&token, &admin, &1000);
+    client.create_package(&admin, &1, &recipient, &600, &token, &0, &0, &None, &None);
+
+    // Revoking unlocks the 600, but it is still owed to the refund destination
+    client.revoke(&1);
+    assert_eq!(client.get_total_locked(&token), 0);
+    assert_eq!(
+        client.try_withdraw_excess(&token, &401),
+        Err(Ok(Error::InsufficientFunds))
+    );
+    client.withdraw_excess(&token, &400);
+    assert_eq!(client.get_token_breakdown(&token).available, 0);
+
+    // The refund can still be paid out in full
+    client.refund(&1);
+    assert_eq!(token_client.balance(&admin), 1000);
+    assert_eq!(token_client.balance(&contract_id), 0);
+}
+
+#[test]
+fn test_disburse_to_alternate_address() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let partner = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_client, token_admin_client) = setup_token(&env, &token_admin);
+
+    let contract_id = env.register(AidEscrow, ());
+    let client = AidEscrowClient::new(&env, &contract_id);
+    client.init(&admin);
+
+    token_admin_client.mint(&admin, &1000);
+    client.fund(&token_client.address, &admin, &1000);
+    client.create_package(
+        &admin,
+        &1,
+        &recipient,
+        &700,
+        &token_client.address,
+        &0,
+        &0,
+        &None,
+        &None,
+    );
+
+    client.disburse_to(&1, &partner);
+
+    let pkg = client.get_package(&1);
+    assert_eq!(pkg.status, PackageStatus::Disbursed);
+    assert_eq!(pkg.recipient, recipient);
+    assert_eq!(token_client.balance(&partner), 700);
+    assert_eq!(token_client.balance(&recipient), 0);
+    assert_eq!(client.get_total_locked(&token_client.address), 0);
+
+    assert_eq!(
+        client.try_disburse_to(&1, &partner),
+        Err(Ok(Error::PackageNotActive))
+    );
+}
+
+#[test]
+fn test_claim_cliff() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let partner = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_client, token_admin_client) = setup_token(&env, &token_admin);
+
+    let contract_id = env.register(AidEscrow, ());
+    let client = AidEscrowClient::new(&env, &contract_id);
+    client.init(&admin);
+
+    token_admin_client.mint(&admin, &1000);
+    client.fund(&token_client.address, &admin, &1000);
+
+    env.ledger().set_timestamp(1000);
+    let start_at = 2000;
+    client.create_package(
+        &admin,
+        &1,
+        &recipient,
+        &300,
+        &token_client.address,
+        &0,
+        &start_at,
+        &None,
+        &None,
+    );
+    client.create_package(
+        &admin,
+        &2,
+        &recipient,
+        &300,
+        &token_client.address,
+        &0,
+        &start_at,
+        &None,
+        &None,
+    );
+    assert_eq!(client.get_package(&1).start_at, start_at);
+
+    // Before the cliff: neither claim nor disburse can release funds
+    env.ledger().set_timestamp(start_at - 1);
+    assert_eq!(client.try_claim(&1), Err(Ok(Error::PackageNotYetClaimable)));
+    assert_eq!(
+        client.try_disburse(&1),
+        Err(Ok(Error::PackageNotYetClaimable))
+    );
+
+    // ...unless the admin explicitly uses disburse_to
+    client.disburse_to(&2, &partner);
+    assert_eq!(token_client.balance(&partner), 300);
+
+    // Exactly at the cliff the package becomes claimable
+    env.ledger().set_timestamp(start_at);
+    client.claim(&1);
+    assert_eq!(token_client.balance(&recipient), 300);
+}
+
+#[test]
+fn test_revoke_batch() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_client, token_admin_client) = setup_token(&env, &token_admin);
+
+    let contract_id = env.register(AidEscrow, ());
+    let client = AidEscrowClient::new(&env, &contract_id);
+    client.init(&admin);
+
+    token_admin_client.mint(&admin, &1000);
+    client.fund(&token_client.address, &admin, &1000);
+    client.create_package(
+        &admin,
+        &1,
+        &recipient,
+        &100,
+        &token_client.address,
+        &0,
+        &0,
+        &None,
+        &None,
+    );
+    client.create_package(
+        &admin,
+        &2,
+        &recipient,
+        &200,
+        &token_client.address,
+        &0,
+        &0,
+        &None,
+        &None,
+    );
+    client.create_package(
+        &admin,
+        &3,
+        &recipient,
+        &300,
+        &token_client.address,
+        &0,
+        &0,
+        &None,
+        &None,
+    );
+    client.claim(&3);
+
+    let result = client.revoke_batch(&vec![&env, 1, 2, 3, 99]);
+
+    assert_eq!(result.successes, vec![&env, 1, 2]);
+    assert_eq!(
+        result.failures,
+        vec![
+            &env,
+            (3, Error::InvalidState as u32),
+            (99, Error::PackageNotFound as u32)
+        ]
+    );
+    assert_eq!(client.get_package(&1).status, PackageStatus::Cancelled);
+    assert_eq!(client.get_package(&2).status, PackageStatus::Cancelled);
+    assert_eq!(client.get_total_locked(&token_client.address), 0);
+    assert_status_counters(&client, &[1, 2, 3]);
+    assert_locked_invariant(&client, &token_client.address, &[1, 2, 3]);
+}
+
+#[test]
+fn test_disburse_batch() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let recipient_a = Address::generate(&env);
+    let recipient_b = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_client, token_admin_client) = setup_token(&env, &token_admin);
+
+    let contract_id = env.register(AidEscrow, ());
+    let client = AidEscrowClient::new(&env, &contract_id);
+    client.init(&admin);
+
+    token_admin_client.mint(&admin, &1000);
+    client.fund(&token_client.address, &admin, &1000);
+    client.create_package(
+        &admin,
+        &1,
+        &recipient_a,
+        &100,
+        &token_client.address,
+        &0,
+        &0,
+        &None,
+        &None,
+    );
+    client.create_package(
+        &admin,
+        &2,
+        &recipient_b,
+        &200,
+        &token_client.address,
+        &0,
+        &0,
+        &None,
+        &None,
+    );
+    client.create_package(
+        &admin,
+        &3,
+        &recipient_a,
+        &300,
+        &token_client.address,
+        &0,
+        &0,
+        &None,
+        &None,
+    );
+    client.revoke(&3);
+
+    let result = client.disburse_batch(&vec![&env, 1, 2, 3, 99]);
+
+    assert_eq!(result.successes, vec![&env, 1, 2]);
+    assert_eq!(
+        result.failures,
+        vec![
+            &env,
+            (3, Error::PackageNotActive as u32),
+            (99, Error::PackageNotFound as u32)
+        ]
+    );
+    assert_eq!(token_client.balance(&recipient_a), 100);
+    assert_eq!(token_client.balance(&recipient_b), 200);
+    assert_eq!(client.get_package(&3).status, PackageStatus::Cancelled);
+    assert_eq!(client.get_total_locked(&token_client.address), 0);
+    assert_status_counters(&client, &[1, 2, 3]);
+    assert_locked_invariant(&client, &token_client.address, &[1, 2, 3]);
+}
+
+/// Checks the recipient locked index against the unclaimed amounts of the active packages in `ids`.
+fn assert_recipient_locked(
+    client: &AidEscrowClient,
+    recipient: &Address,
+    token: &Address,
+    ids: &[u64],
+) {
+    let mut expected = 0;
+    for id in ids {
+        let package = client.get_package(id);
+        if package.status.is_active() && package.recipient == *recipient && package.token == *token
+        {
+            expected += package.remaining_amount;
+        }
+    }
+    assert_eq!(
+        client.get_recipient_total_locked(recipient, token),
+        expected
+    );
+}
+
+#[test]
+fn test_recipient_total_locked_index() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_client, token_admin_client) = setup_token(&env, &token_admin);
+    let token = token_client.address.clone();
+
+    let contract_id = env.register(AidEscrow, ());
+    let client = AidEscrowClient::new(&env, &contract_id);
+    client.init(&admin);
+
+    token_admin_client.mint(&admin, &10_000);
+    client.fund(&token, &admin, &10_000);
+
+    let ids = [1, 2, 3, 4];
+    client.create_package(&admin, &1, &alice, &100, &token, &0, &0, &None, &None);
+    client.create_package(&admin, &2, &alice, &200, &token, &0, &0, &None, &None);
+    client.create_package(&admin, &3, &alice, &300, &token, &0, &0, &None, &None);
+    client.create_package(&admin, &4, &bob, &400, &token, &0, &0, &None, &None);
+    assert_eq!(client.get_recipient_total_locked(&alice, &token), 600);
+
+    client.partial_claim(&1, &40);
+    client.claim(&2);
+    client.revoke(&3);
+    client.increase_package_amount(&4, &50);
+    client.decrease_package_amount(&4, &25);
+    client.reassign_recipient(&1, &bob);
+
+    assert_recipient_locked(&client, &alice, &token, &ids);
+    assert_recipient_locked(&client, &bob, &token, &ids);
+    assert_eq!(client.get_recipient_total_locked(&alice, &token), 0);
+    assert_eq!(client.get_recipient_total_locked(&bob, &token), 485);
+
+    client.disburse(&4);
+    assert_recipient_locked(&client, &bob, &token, &ids);
+    assert_eq!(client.get_recipient_total_locked(&bob, &token), 60);
+}
+
+#[test]
+fn test_contract_stats() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_client, token_admin_client) = setup_token(&env, &token_admin);
+    let token = token_client.address.clone();
+
+    let contract_id = env.register(AidEscrow, ());
+    let client = AidEscrowClient::new(&env, &contract_id);
+    client.init(&admin);
+
+    token_admin_client.mint(&admin, &1000);
+    client.fund(&token, &admin, &1000);
+
+    env.ledger().set_timestamp(1000);
+    client.create_package(&admin, &1, &recipient, &100, &token, &0, &0, &None, &None);
+    client.create_package(&admin, &2, &recipient, &200, &token, &0, &0, &None, &None);
+    client.create_package(
+        &admin, &3, &recipient, &300, &token, &1100, &0, &None, &None,
+    );
+    client.create_package(&admin, &4, &recipient, &50, &token, &0, &0, &None, &None);
+
+    client.claim(&1);
+    client.disburse(&2);
+    env.ledger().set_timestamp(1101);
+    client.refund(&3);
+
+    assert_eq!(
+        client.get_stats(&token),
+        ContractStats {
+            total_funded: 1000,
+            total_disbursed: 300,
+            total_refunded: 300,
+            total_locked: 50,
+            available_balance: 350,
+            packages_created: 4,
+            packages_claimed: 1,
+            packages_disbursed: 1,
+            packages_cancelled: 0,
+            packages_expired: 0,
+            packages_refunded: 1,
+            packages_transferred: 0,
+        }
+    );
+}
+
+#[test]
+fn test_force_expire() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_client, token_admin_client) = setup_token(&env, &token_admin);
+    let token = token_client.address.clone();
+
+    let contract_id = env.register(AidEscrow, ());
+    let client = AidEscrowClient::new(&env, &contract_id);
+    client.init(&admin);
+
+    token_admin_client.mint(&admin, &1000);
+    client.fund(&token, &admin, &1000);
+
+    env.ledger().set_timestamp(1000);
+    client.create_package(
+        &admin, &1, &recipient, &100, &token, &1100, &0, &None, &None,
+    );
+    client.create_package(
+        &admin, &2, &recipient, &200, &token, &1100, &0, &None, &None,
+    );
+    client.create_package(&admin, &3, &recipient, &300, &token, &0, &0, &None, &None);
+    client.create_package(&admin, &4, &recipient, &50, &token, &1100, &0, &None, &None);
+
+    // Deadline not reached yet
+    assert_eq!(
+        client.try_force_expire(&1),
+        Err(Ok(Error::PackageNotExpired))
+    );
+
+    env.ledger().set_timestamp(1101);
+    client.force_expire(&1);
+    assert_eq!(client.get_package(&1).status, PackageStatus::Expired);
+    assert_eq!(client.get_total_locked(&token), 550);
+    assert_locked_invariant(&client, &token, &[1, 2, 3, 4]);
+    assert_eq!(client.try_force_expire(&1), Err(Ok(Error::InvalidState)));
+
+    let result = client.force_expire_batch(&vec![&env, 2, 3, 4, 99]);
+    assert_eq!(result.successes, vec![&env, 2, 4]);
+    assert_eq!(
+        result.failures,
+        vec![
+            &env,
+            (3, Error::PackageNotExpired as u32),
+            (99, Error::PackageNotFound as u32)
+        ]
+    );
+    assert_eq!(client.get_total_locked(&token), 300);
+    assert_status_counters(&client, &[1, 2, 3, 4]);
+
+    // Expired packages can still be refunded
+    client.refund(&2);
+    assert_eq!(token_client.balance(&admin), 200);
+    assert_locked_invariant(&client, &token, &[1, 2, 3, 4]);
+}
+
+#[test]
+fn test_claim_grace_period() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_client, token_admin_client) = setup_token(&env, &token_admin);
+    let token = token_client.address.clone();
+
+    let contract_id = env.register(AidEscrow, ());
+    let client = AidEscrowClient::new(&env, &contract_id);
+    client.init(&admin);
+
+    token_admin_client.mint(&admin, &1000);
+    client.fund(&token, &admin, &1000);
+
+    env.ledger().set_timestamp(1000);
+    for id in 1..=4 {
+        client.create_package(
+            &admin, &id, &recipient, &100, &token, &1100, &0, &None, &None,
+        );
+    }
+    client.create_package(&admin, &5, &recipient, &100, &token, &0, &0, &None, &None);
+
+    // grace_period == 0: hard cutoff at expires_at
+    assert_eq!(client.get_claim_grace_period(), 0);
+    assert_eq!(client.get_claim_deadline(&1), 1100);
+    env.ledger().set_timestamp(1101);
+    assert_eq!(client.try_claim(&1), Err(Ok(Error::PackageExpired)));
+
+    client.set_claim_grace_period(&50);
+    assert_eq!(client.get_claim_deadline(&2), 1150);
+
+    // Within the grace period the recipient can still claim...
+    env.ledger().set_timestamp(1150);
+    client.claim(&2);
+    assert_eq!(client.get_package(&2).status, PackageStatus::Claimed);
+
+    // ...but force_expire still only looks at expires_at
+    client.force_expire(&3);
+    assert_eq!(client.get_package(&3).status, PackageStatus::Expired);
+
+    // After the grace period the package is expired
+    env.ledger().set_timestamp(1151);
+    assert_eq!(client.try_claim(&4), Err(Ok(Error::PackageExpired)));
+
+    // expires_at == 0: no deadline, grace period irrelevant
+    assert_eq!(client.get_claim_deadline(&5), 0);
+    client.claim(&5);
+    assert_eq!(
+        client.try_get_claim_deadline(&99),
+        Err(Ok(Error::PackageNotFound))
+    );
+}
+
+#[test]
+fn test_is_package_claimable() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_client, token_admin_client) = setup_token(&env, &token_admin);
+    let token = token_client.address.clone();
+
+    let contract_id = env.register(AidEscrow, ());
+    let client = AidEscrowClient::new(&env, &contract_id);
+    client.init(&admin);
+
+    token_admin_client.mint(&admin, &1000);
+    client.fund(&token, &admin, &1000);
+
+    env.ledger().set_timestamp(1000);
+    client.create_package(
+        &admin, &1, &recipient, &100, &token, &2000, &1500, &None, &None,
+    );
+    client.create_package(&admin, &2, &recipient, &100, &token, &0, &0, &None, &None);
+
+    // Before the cliff
+    assert!(!client.is_package_claimable(&1));
+    env.ledger().set_timestamp(1500);
+    assert!(client.is_package_claimable(&1));
+
+    // Partially claimed packages remain claimable
+    client.partial_claim(&1, &40);
+    assert!(client.is_package_claimable(&1));
+
+    // Blacklisted recipients cannot claim
+    client.blacklist_recipient(&recipient);
+    assert!(!client.is_package_claimable(&1));
+    client.unblacklist_recipient(&recipient);
+
+    // Past expiry (and grace period)
+    env.ledger().set_timestamp(2001);
+    assert!(!client.is_package_claimable(&1));
+    client.set_claim_grace_period(&100);
+    assert!(client.is_package_claimable(&1));
+
+    // Terminal packages are never claimable; no expiry means always in time
+    client.claim(&2);
+    assert!(!client.is_package_claimable(&2));
+    assert_eq!(
+        client.try_is_package_claimable(&99),
+        Err(Ok(Error::PackageNotFound))
+    );
+}
+
+#[test]
+fn test_packages_expiring_within() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_client, token_admin_client) = setup_token(&env, &token_admin);
+    let (other_token_client, other_token_admin_client) = setup_token(&env, &token_admin);
+    let token = token_client.address.clone();
+
+    let contract_id = env.register(AidEscrow, ());
+    let client = AidEscrowClient::new(&env, &contract_id);
+    client.init(&admin);
+
+    token_admin_client.mint(&admin, &1000);
+    client.fund(&token, &admin, &1000);
+    other_token_admin_client.mint(&admin, &1000);
+    client.fund(&other_token_client.address, &admin, &1000);
+
+    env.ledger().set_timestamp(1000);
+    client.create_package(
+        &admin, &1, &recipient, &100, &token, &1100, &0, &None, &None,
+    );
+    client.create_package(
+        &admin, &2, &recipient, &100, &token, &1500, &0, &None, &None,
+    );
+    client.create_package(&admin, &3, &recipient, &100, &token, &0, &0, &None, &None);
+    client.create_package(
+        &admin, &4, &recipient, &100, &token, &1200, &0, &None, &None,
+    );
+    client.create_package(
+        &admin, &5, &recipient, &100, &token, &1300, &0, &None, &None,
+    );
+    client.create_package(
+        &admin,
+        &6,
+        &recipient,
+        &100,
+        &other_token_client.address,
+        &1100,
+        &0,
+        &None,
+        &None,
+    );
+
+    assert_eq!(
+        client.get_packages_expiring_within(&token, &100),
+        vec![&env, 1]
+    );
+    assert_eq!(
+        client.get_packages_expiring_within(&token, &300),
+        vec![&env, 1, 4, 5]
+    );
+
+    // Terminal packages drop out, updated deadlines move
+    client.claim(&4);
+    client.revoke(&5);
+    client.update_expiry(&2, &1050);
+    assert_eq!(
+        client.get_packages_expiring_within(&token, &300),
+        vec![&env, 1, 2]
+    );
+
+    // Every indexed package is active and within the window
+    let ids = [1, 2, 3, 4, 5];
+    let expiring = client.get_packages_expiring_within(&token, &u64::MAX);
+    for id in ids {
+        let package = client.get_package(&id);
+        let indexed = expiring.contains(id);
+        assert_eq!(
+            indexed,
+            package.status.is_active() && package.expires_at > 0
+        );
+    }
+
+    // Lapsed packages stay listed until they are expired on-chain
+    env.ledger().set_timestamp(1101);
+    client.force_expire(&1);
+    assert_eq!(
+        client.get_packages_expiring_within(&token, &0),
+        vec![&env, 2]
+    );
+
+    // Deadlines weeks apart land in separate buckets, listed earliest week first
+    let week = 604_800;
+    client.create_package(
+        &admin,
+        &7,
+        &recipient,
+        &100,
+        &token,
+        &(3 * week),
+        &0,
+        &None,
+        &None,
+    );
+    client.update_expiry(&2, &(2 * week));
+    assert!(
+        client
+            .get_packages_expiring_within(&token, &week)
+            .is_empty()
+    );
+    assert_eq!(
+        client.get_packages_expiring_within(&token, &(3 * week)),
+        vec![&env, 2, 7]
+    );
+    client.revoke(&2);
+    assert_eq!(
+        client.get_packages_expiring_within(&token, &u64::MAX),
+        vec![&env, 7]
+    );
+}
+
+#[test]
+fn test_rebalance_locked_map() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_client, token_admin_client) = setup_token(&env, &token_admin);
+    let token = token_client.address.clone();
+
+    let contract_id = env.register(AidEscrow, ());
+    let client = AidEscrowClient::new(&env, &contract_id);
+    client.init(&admin);
+
+    token_admin_client.mint(&admin, &1000);
+    client.fund(&token, &admin, &1000);
+    client.set_fee_rate_bps(&token, &1000);
+
+    client.create_package(&admin, &1, &recipient, &100, &token, &0, &0, &None, &None);
+    client.create_package(&admin, &2, &recipient, &200, &token, &0, &0, &None, &None);
+    client.create_package(&admin, &3, &recipient, &300, &token, &0, &0, &None, &None);
+    client.partial_claim(&2, &50); // 5 fee stays locked
+    client.revoke(&3);
+    assert_eq!(client.get_total_locked(&token), 255);
+
+    // Simulate drift in the locked map
+    env.as_contract(&contract_id, || {
+        let mut locked: Map<Address, i128> = Map::new(&env);
+        locked.set(token.clone(), 999);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("locked"), &locked);
+    });
+    assert_eq!(client.get_total_locked(&token), 999);
+
+    let corrected = client.rebalance_locked_map(&token, &vec![&env, 1, 2, 2, 3]);
+    assert_eq!(corrected, 255);
+    assert_eq!(client.get_total_locked(&token), 255);
+
+    assert_eq!(
+        client.try_rebalance_locked_map(&token, &vec![&env, 99]),
+        Err(Ok(Error::PackageNotFound))
+    );
+}
+
+#[test]
+fn test_locked_ratio_bps() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_client, token_admin_client) = setup_token(&env, &token_admin);
+    let token = token_client.address.clone();
+
+    let contract_id = env.register(AidEscrow, ());
+    let client = AidEscrowClient::new(&env, &contract_id);
+    client.init(&admin);
+
+    // Empty pool
+    assert_eq!(client.get_locked_ratio_bps(&token), 0);
+
+    token_admin_client.mint(&admin, &1000);
+    client.fund(&token, &admin, &1000);
+    assert_eq!(client.get_locked_ratio_bps(&token), 0);
+
+    client.create_package(&admin, &1, &recipient, &250, &token, &0, &0, &None, &None);
+    assert_eq!(client.get_locked_ratio_bps(&token), 2500);
+
+    client.create_package(&admin, &2, &recipient, &750, &token, &0, &0, &None, &None);
+    assert_eq!(client.get_locked_ratio_bps(&token), 10_000);
+
+    // Underfunded: locked exceeds the balance, capped at fully utilized
+    env.as_contract(&contract_id, || {
+        TokenClient::new(&env, &token).transfer(&contract_id, &admin, &600);
+    });
+    assert_eq!(client.get_locked_ratio_bps(&token), 10_000);
+
+    // Balance drained while funds are still locked
+    env.as_contract(&contract_id, || {
+        TokenClient::new(&env, &token).transfer(&contract_id, &admin, &400);
+    });
+    assert_eq!(
+        client.try_get_locked_ratio_bps(&token),
+        Err(Ok(Error::InsufficientFunds))
+    );
+}
+
+#[test]
+fn test_emit_health_snapshot() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_client, token_admin_client) = setup_token(&env, &token_admin);
+    let token = token_client.address.clone();
+
+    let contract_id = env.register(AidEscrow, ());
+    let client = AidEscrowClient::new(&env, &contract_id);
+    client.init(&admin);
+
+    token_admin_client.mint(&admin, &1000);
+    client.fund(&token, &admin, &1000);
+    client.create_package(&admin, &1, &recipient, &100, &token, &0, &0, &None, &None);
+    client.create_package(&admin, &2, &recipient, &200, &token, &0, &0, &None, &None);
+    client.claim(&1);
+
+    let stats_before = client.get_stats(&token);
+    let log_before = client.get_admin_log_count();
+
+    client.emit_health_snapshot();
+    let events = env.events().all();
+    assert_eq!(events.len(), 1);
+    assert_eq!(events.get(0).unwrap().0, contract_id);
+
+    // Pure side effect: nothing in storage changes
+    assert_eq!(client.get_stats(&token), stats_before);
+    assert_eq!(client.get_admin_log_count(), log_before);
+}
+
+#[test]
+fn test_package_age_and_time_remaining() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_client, token_admin_client) = setup_token(&env, &token_admin);
+    let token = token_client.address.clone();
+
+    let contract_id = env.register(AidEscrow, ());
+    let client = AidEscrowClient::new(&env, &contract_id);
+    client.init(&admin);
+
+    token_admin_client.mint(&admin, &1000);
+    client.fund(&token, &admin, &1000);
+
+    env.ledger().set_timestamp(1000);
+    client.create_package(
+        &admin, &1, &recipient, &100, &token, &1500, &0, &None, &None,
+    );
+    client.create_package(&admin, &2, &recipient, &100, &token, &0, &0, &None, &None);
+
+    assert_eq!(client.get_package_age(&1), 0);
+    assert_eq!(client.get_time_remaining(&1), Some(500));
+    assert_eq!(client.get_time_remaining(&2), None);
+
+    env.ledger().set_timestamp(1200);
+    assert_eq!(client.get_package_age(&1), 200);
+    assert_eq!(client.get_time_remaining(&1), Some(300));
+
+    env.ledger().set_timestamp(2000);
+    assert_eq!(client.get_time_remaining(&1), Some(0));
+
+    assert_eq!(
+        client.try_get_package_age(&9),
+        Err(Ok(Error::PackageNotFound))
+    );
+    assert_eq!(
+        client.try_get_time_remaining(&9),
+        Err(Ok(Error::PackageNotFound))
+    );
+}
+
+#[test]
+fn test_fund_on_behalf_attribution() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let donor = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_client, token_admin_client) = setup_token(&env, &token_admin);
+    let token = token_client.address.clone();
+
+    let contract_id = env.register(AidEscrow, ());
+    let client = AidEscrowClient::new(&env, &contract_id);
+    client.init(&admin);
+
+    let red_cross = String::from_str(&env, "Red Cross");
+    let unicef = String::from_str(&env, "UNICEF");
+    assert_eq!(client.get_funding_by_org(&red_cross), 0);
+
+    token_admin_client.mint(&donor, &1000);
+    client.fund_on_behalf(&token, &donor, &red_cross, &300);
+    client.fund_on_behalf(&token, &donor, &red_cross, &200);
+    client.fund_on_behalf(&token, &donor, &unicef, &100);
+    client.fund(&token, &donor, &50);
+
+    assert_eq!(client.get_funding_by_org(&red_cross), 500);
+    assert_eq!(client.get_funding_by_org(&unicef), 100);
+
+    // Pool accounting is unaffected by the tag
+    assert_eq!(token_client.balance(&contract_id), 650);
+    assert_eq!(client.get_stats(&token).total_funded, 650);
+
+    assert_eq!(
+        client.try_fund_on_behalf(&token, &donor, &unicef, &0),
+        Err(Ok(Error::InvalidAmount))
+    );
+    assert_eq!(client.get_funding_by_org(&unicef), 100);
+}
+
+#[test]
+fn test_claim_preview() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_client, token_admin_client) = setup_token(&env, &token_admin);
+    let token = token_client.address.clone();
+
+    let contract_id = env.register(AidEscrow, ());
+    let client = AidEscrowClient::new(&env, &contract_id);
+    client.init(&admin);
+
+    token_admin_client.mint(&admin, &1000);
+    client.fund(&token, &admin, &1000);
+    client.set_fee_rate_bps(&token, &250); // 2.5%
+
+    env.ledger().set_timestamp(100);
+    client.create_package(&admin, &1, &recipient, &400, &token, &0, &0, &None, &None);
+    client.create_package(&admin, &2, &recipient, &100, &token, &0, &500, &None, &None);
+    client.create_package(&admin, &3, &recipient, &100, &token, &200, &0, &None, &None);
+
+    assert_eq!(client.try_claim_preview(&1), 390);
+
+    // Preview matches what the claim actually pays, and changes nothing itself
+    assert_eq!(client.get_package(&1).remaining_amount, 400);
+    client.claim(&1);
+    assert_eq!(token_client.balance(&recipient), 390);
+
+    assert_eq!(
+        client.try_try_claim_preview(&1),
+        Err(Ok(Error::PackageNotActive))
+    );
+    assert_eq!(
+        client.try_try_claim_preview(&2),
+        Err(Ok(Error::PackageNotYetClaimable))
+    );
+    assert_eq!(
+        client.try_try_claim_preview(&9),
+        Err(Ok(Error::PackageNotFound))
+    );
+
+    env.ledger().set_timestamp(300);
+    assert_eq!(
+        client.try_try_claim_preview(&3),
+        Err(Ok(Error::PackageExpired))
+    );
+    assert_eq!(client.get_package(&3).status, PackageStatus::Created);
+}
+
+#[test]
+fn test_package_history() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_client, token_admin_client) = setup_token(&env, &token_admin);
+    let token = token_client.address.clone();
+
+    let contract_id = env.register(AidEscrow, ());
+    let client = AidEscrowClient::new(&env, &contract_id);
+    client.init(&admin);
+
+    token_admin_client.mint(&admin, &1000);
+    client.fund(&token, &admin, &1000);
+
+    env.ledger().set_timestamp(100);
+    client.create_package(&admin, &1, &recipient, &100, &token, &0, &0, &None, &None);
+    env.ledger().set_timestamp(200);
+    client.claim(&1);
+
+    let history = client.get_package_history(&1);
+    assert_eq!(history.len(), 2);
+    assert_eq!(
+        history.get(0).unwrap(),
+        StatusTransition {
+            from_status: PackageStatus::Created,
+            to_status: PackageStatus::Created,
+            timestamp: 100,
+            actor: admin.clone(),
+            memo: String::from_str(&env, ""),
+        }
+    );
+    assert_eq!(
+        history.get(1).unwrap(),
+        StatusTransition {
+            from_status: PackageStatus::Created,
+            to_status: PackageStatus::Claimed,
+            timestamp: 200,
+            actor: recipient.clone(),
+            memo: String::from_str(&env, ""),
+        }
+    );
+
+    // Revoke + refund are attributed to the admin
+    client.create_package(&admin, &2, &recipient, &100, &token, &0, &0, &None, &None);
+    client.revoke(&2);
+    client.refund(&2);
+    let history = client.get_package_history(&2);
+    assert_eq!(history.len(), 3);
+    let last = history.get(2).unwrap();
+    assert_eq!(last.from_status, PackageStatus::Cancelled);
+    assert_eq!(last.to_status, PackageStatus::Refunded);
+    assert_eq!(last.actor, admin);
+
+    assert_eq!(
+        client.try_get_package_history(&9),
+        Err(Ok(Error::PackageNotFound))
+    );
+}
+
+#[test]
+fn test_claim_with_memo() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_client, token_admin_client) = setup_token(&env, &token_admin);
+    let token = token_client.address.clone();
+
+    let contract_id = env.register(AidEscrow, ());
+    let client = AidEscrowClient::new(&env, &contract_id);
+    client.init(&admin);
+
+    token_admin_client.mint(&admin, &1000);
+    client.fund(&token, &admin, &1000);
+    client.create_package(&admin, &1, &recipient, &100, &token, &0, &0, &None, &None);
+
+    let too_long = String::from_str(&env, &"x".repeat(257));
+    assert_eq!(
+        client.try_claim_with_memo(&1, &too_long),
+        Err(Ok(Error::MemoTooLong))
+    );
+    assert_eq!(client.get_package(&1).status, PackageStatus::Created);
+
+    let memo = String::from_str(&env, "Received, used for school fees");
+    client.claim_with_memo(&1, &memo);
+    assert_eq!(token_client.balance(&recipient), 100);
+
+    let history = client.get_package_history(&1);
+    let claim = history.get(1).unwrap();
+    assert_eq!(claim.to_status, PackageStatus::Claimed);
+    assert_eq!(claim.memo, memo);
+}
+
+#[test]
+fn test_donate_to_pool() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_client, token_admin_client) = setup_token(&env, &token_admin);
+    let token = token_client.address.clone();
+
+    let contract_id = env.register(AidEscrow, ());
+    let client = AidEscrowClient::new(&env, &contract_id);
+    client.init(&admin);
+
+    token_admin_client.mint(&admin, &1000);
+    token_admin_client.mint(&alice, &1000);
+    token_admin_client.mint(&bob, &1000);
+
+    client.fund(&token, &admin, &500);
+    client.donate_to_pool(&token, &alice, &200);
+    client.donate_to_pool(&token, &alice, &100);
+    client.donate_to_pool(&token, &bob, &50);
+
+    assert_eq!(client.get_total_donations(&token), 350);
+    assert_eq!(client.get_donor_amount(&alice, &token), 300);
+    assert_eq!(client.get_donor_amount(&bob, &token), 50);
+    assert_eq!(client.get_donor_amount(&admin, &token), 0);
+
+    // Donations back packages just like operational funds
+    assert_eq!(token_client.balance(&contract_id), 850);
+    client.create_package(&admin, &1, &recipient, &850, &token, &0, &0, &None, &None);
+
+    assert_eq!(
+        client.try_donate_to_pool(&token, &bob, &0),
+        Err(Ok(Error::InvalidAmount))
+    );
+}
+
+#[test]
+fn test_multi_token_solvency() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_a_client, token_a_admin) = setup_token(&env, &token_admin);
+    let (token_b_client, token_b_admin) = setup_token(&env, &token_admin);
+    let (token_a, token_b) = (
+        token_a_client.address.clone(),
+        token_b_client.address.clone(),
+    );
+
+    let contract_id = env.register(AidEscrow, ());
+    let client = AidEscrowClient::new(&env, &contract_id);
+    client.init(&admin);
+
+    token_a_admin.mint(&admin, &1000);
+    token_b_admin.mint(&admin, &2000);
+    client.fund(&token_a, &admin, &1000);
+    client.fund(&token_b, &admin, &2000);
+
+    let ids = [1, 2, 3, 4, 5, 6];
+    client.create_package(&admin, &1, &recipient, &100, &token_a, &0, &0, &None, &None);
+    client.create_package(&admin, &2, &recipient, &500, &token_b, &0, &0, &None, &None);
+    client.create_package(&admin, &3, &recipient, &200, &token_a, &0, &0, &None, &None);
+    client.create_package(&admin, &4, &recipient, &700, &token_b, &0, &0, &None, &None);
+    client.create_package(&admin, &5, &recipient, &300, &token_a, &0, &0, &None, &None);
+    client.create_package(&admin, &6, &recipient, &400, &token_b, &0, &0, &None, &None);
+    assert_eq!(client.get_total_locked(&token_a), 600);
+    assert_eq!(client.get_total_locked(&token_b), 1600);
+
+    // Each mutation touches one token's entry and must leave the other intact
+    client.claim(&1);
+    assert_locked_invariant(&client, &token_a, &ids);
+    assert_locked_invariant(&client, &token_b, &ids);
+
+    client.revoke(&4);
+    assert_locked_invariant(&client, &token_a, &ids);
+    assert_locked_invariant(&client, &token_b, &ids);
+
+    client.partial_claim(&2, &200);
+    client.revoke(&5);
+    assert_locked_invariant(&client, &token_a, &ids);
+    assert_locked_invariant(&client, &token_b, &ids);
+
+    client.refund(&4);
+    client.claim(&6);
+    assert_locked_invariant(&client, &token_a, &ids);
+    assert_locked_invariant(&client, &token_b, &ids);
+
+    assert_eq!(client.get_total_locked(&token_a), 200);
+    assert_eq!(client.get_total_locked(&token_b), 300);
+
+    // Per-token solvency: freed token_b funds cannot back token_a packages
+    assert_eq!(
+        client.try_create_package(&admin, &7, &recipient, &701, &token_a, &0, &0, &None, &None),
+        Err(Ok(Error::InsufficientFunds))
+    );
+    client.create_package(&admin, &7, &recipient, &700, &token_a, &0, &0, &None, &None);
+    assert_eq!(client.get_available_balance(&token_a), 0);
+    assert_eq!(client.get_available_balance(&token_b), 400);
+}
+
+/// Checks that every unit funded into the pool is accounted for in the breakdown.
+fn assert_breakdown_balances(client: &AidEscrowClient, token: &Address) -> TokenBreakdown {
+    let b = client.get_token_breakdown(token);
+    assert_eq!(
+        b.total_funded,
+        b.total_claimed + b.total_refunded + b.total_cancelled + b.total_locked + b.available
+    );
+    b
+}
+
+#[test]
+fn test_token_breakdown() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_client, token_admin_client) = setup_token(&env, &token_admin);
+    let (other_client, other_admin_client) = setup_token(&env, &token_admin);
+    let token = token_client.address.clone();
+
+    let contract_id = env.register(AidEscrow, ());
+    let client = AidEscrowClient::new(&env, &contract_id);
+    client.init(&admin);
+
+    token_admin_client.mint(&admin, &2000);
+    other_admin_client.mint(&admin, &500);
+    client.fund(&token, &admin, &2000);
+    client.fund(&other_client.address, &admin, &500);
+    client.set_fee_rate_bps(&token, &1000); // 10%
+
+    env.ledger().set_timestamp(1000);
+    client.create_package(&admin, &1, &recipient, &400, &token, &0, &0, &None, &None);
+    client.create_package(&admin, &2, &recipient, &300, &token, &0, &0, &None, &None);
+    client.create_package(
+        &admin, &3, &recipient, &200, &token, &1100, &0, &None, &None,
+    );
+    client.create_package(&admin, &4, &recipient, &100, &token, &0, &0, &None, &None);
+    client.create_package(
+        &admin,
+        &5,
+        &recipient,
+        &500,
+        &other_client.address,
+        &0,
+        &0,
+        &None,
+        &None,
+    );
+    assert_breakdown_balances(&client, &token);
+
+    client.claim(&1); // 360 net to the recipient, 40 fee stays locked
+    client.revoke(&2);
+    env.ledger().set_timestamp(1200);
+    client.force_expire(&3);
+    let b = assert_breakdown_balances(&client, &token);
+    assert_eq!(
+        b,
+        TokenBreakdown {
+            total_locked: 140,
+            total_claimed: 360,
+            total_refunded: 0,
+            total_cancelled: 500,
+            total_funded: 2000,
+            available: 1000,
+        }
+    );
+
+    client.refund(&2);
+    client.refund(&3);
+    let b = assert_breakdown_balances(&client, &token);
+    assert_eq!(b.total_refunded, 500);
+    assert_eq!(b.total_cancelled, 0);
+    assert_eq!(b.available, 1000);
+
+    // The other token is tracked independently
+    let other = assert_breakdown_balances(&client, &other_client.address);
+    assert_eq!(other.total_locked, 500);
+    assert_eq!(other.total_claimed, 0);
+    assert_eq!(other.available, 0);
+}
+
+#[test]
+fn test_create_package_and_fund() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let operator = Address::generate(&env);
+    let donor = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_client, token_admin_client) = setup_token(&env, &token_admin);
+    let token = token_client.address.clone();
+
+    let contract_id = env.register(AidEscrow, ());
+    let client = AidEscrowClient::new(&env, &contract_id);
+    client.init(&admin);
+    client.add_operator(&operator);
+
+    token_admin_client.mint(&operator, &1000);
+    token_admin_client.mint(&donor, &1000);
+
+    client.create_package_and_fund(&1, &recipient, &400, &token, &operator, &0);
+    assert_eq!(token_client.balance(&contract_id), 400);
+    assert_eq!(token_client.balance(&operator), 600);
+    assert_eq!(client.get_total_locked(&token), 400);
+    assert_eq!(client.get_package(&1).amount, 400);
+
+    // Atomic: a failed create leaves no deposit behind
+    assert_eq!(
+        client.try_create_package_and_fund(&1, &recipient, &300, &token, &operator, &0),
+        Err(Ok(Error::PackageIdExists))
+    );
+    assert_eq!(token_client.balance(&operator), 600);
+    assert_eq!(token_client.balance(&contract_id), 400);
+
+    // Plain funders cannot create packages through this path
+    assert_eq!(
+        client.try_create_package_and_fund(&2, &recipient, &300, &token, &donor, &0),
+        Err(Ok(Error::NotAuthorized))
+    );
+    assert_eq!(token_client.balance(&donor), 1000);
+
+    client.claim(&1);
+    assert_eq!(token_client.balance(&recipient), 400);
+}
+
+#[test]
+fn test_recipient_cancel_package() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let outsider = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_client, token_admin_client) = setup_token(&env, &token_admin);
+    let token = token_client.address.clone();
+
+    let contract_id = env.register(AidEscrow, ());
+    let client = AidEscrowClient::new(&env, &contract_id);
+    client.init(&admin);
+
+    token_admin_client.mint(&admin, &1000);
+    client.fund(&token, &admin, &1000);
+    client.create_package(&admin, &1, &recipient, &400, &token, &0, &0, &None, &None);
+    client.create_package(&admin, &2, &recipient, &400, &token, &0, &0, &None, &None);
+    client.partial_claim(&2, &100);
+
+    // Only the recipient's signature is accepted
+    env.mock_auths(&[MockAuth {
+        address: &outsider,
+        invoke: &MockAuthInvoke {
+            contract: &client.address,
+            fn_name: "cancel_package",
+            args: (1_u64,).into_val(&env),
+            sub_invokes: &[],
+        },
+    }]);
+    assert!(client.try_cancel_package(&1).is_err());
+    assert_eq!(client.get_package(&1).status, PackageStatus::Created);
+
+    env.mock_all_auths();
+    client.cancel_package(&1);
+    assert_eq!(client.get_package(&1).status, PackageStatus::Cancelled);
+    assert_eq!(client.get_total_locked(&token), 300);
+    assert_locked_invariant(&client, &token, &[1, 2]);
+
+    // Already cancelled, or partially claimed: no longer `Created`
+    assert_eq!(
+        client.try_cancel_package(&1),
+        Err(Ok(Error::PackageNotActive))
+    );
+    assert_eq!(
+        client.try_cancel_package(&2),
+        Err(Ok(Error::PackageNotActive))
+    );
+
+    // The admin keeps its own `revoke` path
+    client.revoke(&2);
+    assert_eq!(client.get_package(&2).status, PackageStatus::Cancelled);
+}
+
+#[test]
+fn test_claim_on_behalf_requires_both_signatures() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let claimer = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_client, token_admin_client) = setup_token(&env, &token_admin);
+    let token = token_client.address.clone();
+
+    let contract_id = env.register(AidEscrow, ());
+    let client = AidEscrowClient::new(&env, &contract_id);
+    client.init(&admin);
+
+    token_admin_client.mint(&admin, &1000);
+    client.fund(&token, &admin, &1000);
+    client.create_package(&admin, &1, &recipient, &400, &token, &0, &0, &None, &None);
+
+    let invoke = MockAuthInvoke {
+        contract: &client.address,
+        fn_name: "claim_on_behalf",
+        args: (1_u64, claimer.clone()).into_val(&env),
+        sub_invokes: &[],
+    };
+
+    // A single signature from either party is not enough
+    for signer in [&claimer, &recipient] {
+        env.mock_auths(&[MockAuth {
+            address: signer,
+            invoke: &invoke,
+        }]);
+        assert!(client.try_claim_on_behalf(&1, &claimer).is_err());
+    }
+    assert_eq!(client.get_package(&1).status, PackageStatus::Created);
+
+    env.mock_auths(&[
+        MockAuth {
+            address: &claimer,
+            invoke: &invoke,
+        },
+        MockAuth {
+            address: &recipient,
+            invoke: &invoke,
+        },
+    ]);
+    client.claim_on_behalf(&1, &claimer);
+
+    assert_eq!(client.get_package(&1).status, PackageStatus::Claimed);
+    assert_eq!(token_client.balance(&recipient), 400);
+    assert_eq!(token_client.balance(&claimer), 0);
+    assert_eq!(client.get_total_locked(&token), 0);
+}
+
+#[test]
+fn test_package_solvency_status() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let elsewhere = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_client, token_admin_client) = setup_token(&env, &token_admin);
+    let token = token_client.address.clone();
+
+    let contract_id = env.register(AidEscrow, ());
+    let client = AidEscrowClient::new(&env, &contract_id);
+    client.init(&admin);
+
+    token_admin_client.mint(&admin, &1000);
+    client.fund(&token, &admin, &1000);
+    client.create_package(&admin, &1, &recipient, &600, &token, &0, &0, &None, &None);
+    client.create_package(&admin, &2, &recipient, &100, &token, &0, &0, &None, &None);
+    client.claim(&2);
+
+    assert_eq!(
+        client.get_package_solvency_status(&1),
+        SolvencyStatus::Solvent
+    );
+    assert_eq!(
+        client.get_package_solvency_status(&2),
+        SolvencyStatus::NotApplicable
+    );
+    assert_eq!(
+        client.try_get_package_solvency_status(&99),
+        Err(Ok(Error::PackageNotFound))
+    );
+
+    // Simulate funds leaking out of the contract behind the locked map's back
+    env.as_contract(&contract_id, || {
+        token_client.transfer(&contract_id, &elsewhere, &500);
+    });
+    assert_eq!(
+        client.get_package_solvency_status(&1),
+        SolvencyStatus::Underfunded
+    );
+}
+
+#[test]
+fn test_disbursed_status_is_distinct_from_claimed() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_client, token_admin_client) = setup_token(&env, &token_admin);
+    let token = token_client.address.clone();
+
+    let contract_id = env.register(AidEscrow, ());
+    let client = AidEscrowClient::new(&env, &contract_id);
+    client.init(&admin);
+
+    token_admin_client.mint(&admin, &1000);
+    client.fund(&token, &admin, &1000);
+    for id in 1..=3u64 {
+        client.create_package(&admin, &id, &recipient, &100, &token, &0, &0, &None, &None);
+    }
+
+    client.claim(&1);
+    client.disburse(&2);
+    client.disburse_to(&3, &admin);
+    assert_eq!(client.get_package(&1).status, PackageStatus::Claimed);
+    assert_eq!(client.get_package(&2).status, PackageStatus::Disbursed);
+    assert_eq!(client.get_package(&3).status, PackageStatus::Disbursed);
+    assert_status_counters(&client, &[1, 2, 3]);
+
+    // Disbursed is terminal
+    assert_eq!(client.try_claim(&2), Err(Ok(Error::PackageNotActive)));
+    assert_eq!(client.try_disburse(&2), Err(Ok(Error::PackageNotActive)));
+    assert_eq!(client.try_revoke(&2), Err(Ok(Error::InvalidState)));
+    assert_eq!(client.try_refund(&2), Err(Ok(Error::InvalidState)));
+    assert_locked_invariant(&client, &token, &[1, 2, 3]);
+}
+
+#[test]
+fn test_double_claim_prevented() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_client, token_admin_client) = setup_token(&env, &token_admin);
+    let token = token_client.address.clone();
+
+    let contract_id = env.register(AidEscrow, ());
+    let client = AidEscrowClient::new(&env, &contract_id);
+    client.init(&admin);
+
+    token_admin_client.mint(&admin, &1000);
+    client.fund(&token, &admin, &1000);
+    client.create_package(&admin, &1, &recipient, &400, &token, &0, &0, &None, &None);
+    client.claim(&1);
+    assert_eq!(token_client.balance(&recipient), 400);
+
+    // A second claim must not pay out again
+    assert_eq!(client.try_claim(&1), Err(Ok(Error::PackageNotActive)));
+    assert_eq!(client.get_package(&1).status, PackageStatus::Claimed);
+    assert_eq!(token_client.balance(&recipient), 400);
+    assert_eq!(token_client.balance(&contract_id), 600);
+    assert_status_counters(&client, &[1]);
+    assert_locked_invariant(&client, &token, &[1]);
+}
+
+#[test]
+fn test_disburse_after_claim_fails() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_client, token_admin_client) = setup_token(&env, &token_admin);
+    let token = token_client.address.clone();
+
+    let contract_id = env.register(AidEscrow, ());
+    let client = AidEscrowClient::new(&env, &contract_id);
+    client.init(&admin);
+
+    token_admin_client.mint(&admin, &1000);
+    client.fund(&token, &admin, &1000);
+    client.create_package(&admin, &1, &recipient, &400, &token, &0, &0, &None, &None);
+    client.claim(&1);
+    assert_eq!(token_client.balance(&recipient), 400);
+
+    // The admin cannot force a second payout
+    assert_eq!(client.try_disburse(&1), Err(Ok(Error::PackageNotActive)));
+    assert_eq!(client.get_package(&1).status, PackageStatus::Claimed);
+    assert_eq!(token_client.balance(&recipient), 400);
+    assert_eq!(token_client.balance(&contract_id), 600);
+    assert_status_counters(&client, &[1]);
+    assert_locked_invariant(&client, &token, &[1]);
+}
+
+#[test]
+fn test_revoke_after_claim_fails() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_client, token_admin_client) = setup_token(&env, &token_admin);
+    let token = token_client.address.clone();
+
+    let contract_id = env.register(AidEscrow, ());
+    let client = AidEscrowClient::new(&env, &contract_id);
+    client.init(&admin);
+
+    token_admin_client.mint(&admin, &1000);
+    client.fund(&token, &admin, &1000);
+    client.create_package(&admin, &1, &recipient, &400, &token, &0, &0, &None, &None);
+    client.claim(&1);
+    assert_eq!(token_client.balance(&recipient), 400);
+
+    // A claimed package cannot be cancelled back into the pool
+    assert_eq!(client.try_revoke(&1), Err(Ok(Error::InvalidState)));
+    assert_eq!(client.get_package(&1).status, PackageStatus::Claimed);
+    assert_eq!(token_client.balance(&recipient), 400);
+    assert_eq!(token_client.balance(&contract_id), 600);
+    assert_status_counters(&client, &[1]);
+    assert_locked_invariant(&client, &token, &[1]);
+}
+
+#[test]
+fn test_concurrent_package_creation_exhausts_solvency() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_client, token_admin_client) = setup_token(&env, &token_admin);
+    let token = token_client.address.clone();
+
+    let contract_id = env.register(AidEscrow, ());
+    let client = AidEscrowClient::new(&env, &contract_id);
+    client.init(&admin);
+
+    token_admin_client.mint(&admin, &100);
+    client.fund(&token, &admin, &100);
+
+    for id in 1..=100u64 {
+        let before = client.get_available_balance(&token);
+        client.create_package(&admin, &id, &recipient, &1, &token, &0, &0, &None, &None);
+        assert_eq!(client.get_available_balance(&token), before - 1);
+        assert_eq!(client.get_total_locked(&token), id as i128);
+    }
+
+    assert_eq!(
+        client.try_create_package(&admin, &101, &recipient, &1, &token, &0, &0, &None, &None),
+        Err(Ok(Error::InsufficientFunds))
+    );
+    assert_eq!(client.get_available_balance(&token), 0);
+    assert_eq!(
+        client.get_total_locked(&token),
+        token_client.balance(&contract_id)
+    );
+}
+
+#[test]
+fn test_created_and_fund_events_carry_timestamps() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_client, token_admin_client) = setup_token(&env, &token_admin);
+    let token = token_client.address.clone();
+
+    let contract_id = env.register(AidEscrow, ());
+    let client = AidEscrowClient::new(&env, &contract_id);
+    client.init(&admin);
+    token_admin_client.mint(&admin, &1000);
+
+    // Reads a `u64` field from the last event this contract published under `topic`
+    let last_field = |topic: &str, field: &str| -> u64 {
+        let topic = Symbol::new(&env, topic);
+        let (_, _, data) = env
+            .events()
+            .all()
+            .iter()
+            .rev()
+            .find(|(contract, topics, _)| {
+                let first = topics
+                    .get(0)
+                    .and_then(|t| Symbol::try_from_val(&env, &t).ok());
+                *contract == contract_id && first == Some(topic.clone())
+            })
+            .unwrap();
+        let data: Map<Symbol, Val> = data.into_val(&env);
+        data.get(Symbol::new(&env, field)).unwrap().into_val(&env)
+    };
+
+    env.ledger().set_timestamp(1000);
+    client.fund(&token, &admin, &1000);
+    assert_eq!(last_field("fund_v2", "timestamp"), 1000);
+
+    env.ledger().set_timestamp(1500);
+    client.create_package(
+        &admin, &1, &recipient, &100, &token, &9000, &0, &None, &None,
+    );
+    assert_eq!(last_field("package_created_v2", "created_at"), 1500);
+    assert_eq!(last_field("package_created_v2", "expires_at"), 9000);
+}
+
+#[test]
+fn test_claimed_event_records_claimant() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let delegate = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_client, token_admin_client) = setup_token(&env, &token_admin);
+    let token = token_client.address.clone();
+
+    let contract_id = env.register(AidEscrow, ());
+    let client = AidEscrowClient::new(&env, &contract_id);
+    client.init(&admin);
+
+    token_admin_client.mint(&admin, &1000);
+    client.fund(&token, &admin, &1000);
+    client.create_package(&admin, &1, &recipient, &100, &token, &0, &0, &None, &None);
+    client.create_package(&admin, &2, &recipient, &100, &token, &0, &0, &None, &None);
+
+    // (recipient, claimant) of the last `ClaimedEvent` published by the contract
+    let last_claim = || -> (Address, Address) {
+        let topic = Symbol::new(&env, "claimed_event");
+        let (_, _, data) = env
+            .events()
+            .all()
+            .iter()
+            .rev()
+            .find(|(contract, topics, _)| {
+                let first = topics
+                    .get(0)
+                    .and_then(|t| Symbol::try_from_val(&env, &t).ok());
+                *contract == contract_id && first == Some(topic.clone())
+            })
+            .unwrap();
+        let data: Map<Symbol, Val> = data.into_val(&env);
+        (
+            data.get(symbol_short!("recipient")).unwrap().into_val(&env),
+            data.get(symbol_short!("claimant")).unwrap().into_val(&env),
+        )
+    };
+
+    client.claim(&1);
+    assert_eq!(last_claim(), (recipient.clone(), recipient.clone()));
+
+    client.delegate_claim(&2, &delegate);
+    client.claim_as_delegate(&2);
+    assert_eq!(last_claim(), (recipient.clone(), delegate));
+}
+
+#[test]
+fn test_token_balance_conservation() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_client, token_admin_client) = setup_token(&env, &token_admin);
+    let token = token_client.address.clone();
+
+    let contract_id = env.register(AidEscrow, ());
+    let client = AidEscrowClient::new(&env, &contract_id);
+    client.init(&admin);
+
+    // Stellar asset contracts expose no `total_supply`, so the minted amount is the reference
+    let minted: i128 = 10_000;
+    token_admin_client.mint(&admin, &minted);
+    let holders = [
+        contract_id.clone(),
+        admin.clone(),
+        alice.clone(),
+        bob.clone(),
+    ];
+    let assert_conserved = |step: &str| {
+        let held: i128 = holders.iter().map(|h| token_client.balance(h)).sum();
+        assert_eq!(held, minted, "tokens created or destroyed after {step}");
+        assert!(client.get_total_locked(&token) <= token_client.balance(&contract_id));
+    };
+    assert_conserved("mint");
+
+    client.fund(&token, &admin, &6000);
+    assert_conserved("fund");
+
+    env.ledger().set_timestamp(1000);
+    for (id, recipient, amount, expires_at) in [
+        (1, &alice, 1000, 0),
+        (2, &bob, 1500, 0),
+        (3, &alice, 500, 2000),
+        (4, &bob, 700, 0),
+        (5, &alice, 800, 0),
+        (6, &bob, 300, 2000),
+    ] {
+        client.create_package(
+            &admin,
+            &id,
+            recipient,
+            &amount,
+            &token,
+            &expires_at,
+            &0,
+            &None,
+            &None,
+        );
+        assert_conserved("create");
+    }
+
+    client.claim(&1);
+    assert_conserved("claim");
+    client.partial_claim(&2, &400);
+    assert_conserved("partial claim");
+    client.disburse(&4);
+    assert_conserved("disburse");
+    client.revoke(&5);
+    assert_conserved("revoke");
+    client.refund(&5);
+    assert_conserved("refund after revoke");
+
+    env.ledger().set_timestamp(2001);
+    client.refund(&3);
+    assert_conserved("refund after expiry");
+    client.force_expire(&6);
+    assert_conserved("force expire");
+    client.refund(&6);
+    assert_conserved("refund after force expire");
+    client.claim(&2);
+    assert_conserved("final claim");
+
+    assert_eq!(token_client.balance(&alice), 1000);
+    assert_eq!(token_client.balance(&bob), 1500 + 700);
+    assert_eq!(client.get_total_locked(&token), 0);
+    assert_locked_invariant(&client, &token, &[1, 2, 3, 4, 5, 6]);
+}
+
+#[test]
+fn test_simulate_claim() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_client, token_admin_client) = setup_token(&env, &token_admin);
+    let token = token_client.address.clone();
+
+    let contract_id = env.register(AidEscrow, ());
+    let client = AidEscrowClient::new(&env, &contract_id);
+    client.init(&admin);
+
+    token_admin_client.mint(&admin, &1000);
+    client.fund(&token, &admin, &1000);
+    client.set_fee_rate_bps(&token, &250); // 2.5%
+
+    env.ledger().set_timestamp(100);
+    client.create_package(&admin, &1, &recipient, &400, &token, &0, &0, &None, &None);
+    client.create_package(&admin, &2, &recipient, &200, &token, &0, &500, &None, &None);
+
+    assert_eq!(
+        client.simulate_claim(&1),
+        ClaimSimulation {
+            gross_amount: 400,
+            fee_amount: 10,
+            net_amount: 390,
+            fee_rate_bps: 250,
+            is_claimable: true,
+            failure_reason: None,
+        }
+    );
+
+    // Not claimable yet: the breakdown is still returned alongside the reason
+    assert_eq!(
+        client.simulate_claim(&2),
+        ClaimSimulation {
+            gross_amount: 200,
+            fee_amount: 5,
+            net_amount: 195,
+            fee_rate_bps: 250,
+            is_claimable: false,
+            failure_reason: Some(Error::PackageNotYetClaimable as u32),
+        }
+    );
+
+    let missing = client.simulate_claim(&9);
+    assert!(!missing.is_claimable);
+    assert_eq!(missing.failure_reason, Some(Error::PackageNotFound as u32));
+    assert_eq!(missing.gross_amount, 0);
+
+    // The simulation matches what the claim pays
+    client.claim(&1);
+    assert_eq!(token_client.balance(&recipient), 390);
+    assert_eq!(
+        client.simulate_claim(&1).failure_reason,
+        Some(Error::PackageNotActive as u32)
+    );
+}
+
+#[test]
+fn test_package_lifecycle_duration() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_client, token_admin_client) = setup_token(&env, &token_admin);
+    let token = token_client.address.clone();
+
+    let contract_id = env.register(AidEscrow, ());
+    let client = AidEscrowClient::new(&env, &contract_id);
+    client.init(&admin);
+
+    token_admin_client.mint(&admin, &1000);
+    client.fund(&token, &admin, &1000);
+
+    env.ledger().set_timestamp(1000);
+    for id in 1..=4u64 {
+        client.create_package(
+            &admin, &id, &recipient, &100, &token, &5000, &0, &None, &None,
+        );
+    }
+    assert_eq!(client.get_package_lifecycle_duration(&1), None);
+
+    env.ledger().set_timestamp(1200);
+    client.partial_claim(&1, &50);
+    assert_eq!(client.get_package_lifecycle_duration(&1), None);
+    client.revoke(&3);
+
+    env.ledger().set_timestamp(1500);
+    client.claim(&1);
+    client.disburse(&2);
+    assert_eq!(client.get_package_lifecycle_duration(&1), Some(500));
+    assert_eq!(client.get_package_lifecycle_duration(&2), Some(500));
+    assert_eq!(client.get_package_lifecycle_duration(&3), Some(200));
+
+    // A refund completes the lifecycle later than the revoke did
+    env.ledger().set_timestamp(1800);
+    client.refund(&3);
+    assert_eq!(client.get_package_lifecycle_duration(&3), Some(800));
+
+    env.ledger().set_timestamp(5001);
+    client.force_expire(&4);
+    assert_eq!(client.get_package_lifecycle_duration(&4), Some(4001));
+
+    assert_eq!(
+        client.try_get_package_lifecycle_duration(&99),
+        Err(Ok(Error::PackageNotFound))
+    );
 }
@@ -0,0 +1,110 @@
+#![cfg(test)]
+
+use aid_escrow::{AidEscrow, AidEscrowClient, Error, PackageStatus};
+use soroban_sdk::{
+    Address, Env,
+    testutils::{Address as _, Ledger},
+    token::{StellarAssetClient, TokenClient},
+};
+
+fn setup_token(env: &Env, admin: &Address) -> (TokenClient<'static>, StellarAssetClient<'static>) {
+    let token_contract = env.register_stellar_asset_contract_v2(admin.clone());
+    let token_client = TokenClient::new(env, &token_contract.address());
+    let token_admin_client = StellarAssetClient::new(env, &token_contract.address());
+    (token_client, token_admin_client)
+}
+
+#[test]
+fn test_refund_after_auto_expiry_with_grace_period() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_client, token_admin_client) = setup_token(&env, &token_admin);
+    let token = token_client.address.clone();
+
+    let contract_id = env.register(AidEscrow, ());
+    let client = AidEscrowClient::new(&env, &contract_id);
+    client.init(&admin);
+
+    token_admin_client.mint(&admin, &1000);
+    client.fund(&token, &admin, &1000);
+
+    let expires_at = 2000;
+    let grace = 100;
+    env.ledger().set_timestamp(1000);
+    client.set_claim_grace_period(&grace);
+    for id in 1..=3u64 {
+        client.create_package(
+            &admin,
+            &id,
+            &recipient,
+            &100,
+            &token,
+            &expires_at,
+            &0,
+            &None,
+            &None,
+        );
+    }
+
+    // Just past the deadline but inside the grace period: the recipient can still claim
+    env.ledger().set_timestamp(expires_at + 1);
+    assert_eq!(client.try_claim(&1), Ok(Ok(())));
+    assert_eq!(client.get_package(&1).status, PackageStatus::Claimed);
+
+    // ...while force_expire ignores the grace period and expires immediately
+    client.force_expire(&2);
+    assert_eq!(client.get_package(&2).status, PackageStatus::Expired);
+    assert_eq!(client.try_claim(&2), Err(Ok(Error::PackageNotActive)));
+    client.refund(&2);
+    assert_eq!(client.get_package(&2).status, PackageStatus::Refunded);
+
+    // Once the grace period is over, claims fail and the admin can refund
+    env.ledger().set_timestamp(expires_at + grace + 1);
+    assert_eq!(client.try_claim(&3), Err(Ok(Error::PackageExpired)));
+    client.refund(&3);
+    assert_eq!(client.get_package(&3).status, PackageStatus::Refunded);
+
+    assert_eq!(token_client.balance(&recipient), 100);
+    assert_eq!(client.get_total_locked(&token), 0);
+}
+
+#[test]
+fn test_zero_expiry_never_expires() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_client, token_admin_client) = setup_token(&env, &token_admin);
+    let token = token_client.address.clone();
+
+    let contract_id = env.register(AidEscrow, ());
+    let client = AidEscrowClient::new(&env, &contract_id);
+    client.init(&admin);
+
+    token_admin_client.mint(&admin, &1000);
+    client.fund(&token, &admin, &1000);
+
+    env.ledger().set_timestamp(1000);
+    client.create_package(&admin, &1, &recipient, &100, &token, &0, &0, &None, &None);
+    assert_eq!(client.get_time_remaining(&1), None);
+
+    // Far in the future the package is still neither expirable nor refundable...
+    env.ledger().set_timestamp(u64::MAX / 2);
+    assert_eq!(client.get_time_remaining(&1), None);
+    assert_eq!(
+        client.try_force_expire(&1),
+        Err(Ok(Error::PackageNotExpired))
+    );
+    assert_eq!(client.try_refund(&1), Err(Ok(Error::InvalidState)));
+
+    // ...and the recipient can still claim it
+    client.claim(&1);
+    assert_eq!(client.get_package(&1).status, PackageStatus::Claimed);
+    assert_eq!(token_client.balance(&recipient), 100);
+}
@@ -0,0 +1,135 @@
+#![cfg(test)]
+
+use std::collections::BTreeSet;
+
+use aid_escrow::{AidEscrow, AidEscrowClient, Error};
+use soroban_sdk::{
+    Address, Env,
+    testutils::Address as _,
+    token::{StellarAssetClient, TokenClient},
+};
+
+fn setup_token(env: &Env, admin: &Address) -> (TokenClient<'static>, StellarAssetClient<'static>) {
+    let token_contract = env.register_stellar_asset_contract_v2(admin.clone());
+    let token_client = TokenClient::new(env, &token_contract.address());
+    let token_admin_client = StellarAssetClient::new(env, &token_contract.address());
+    (token_client, token_admin_client)
+}
+
+/// Small deterministic xorshift generator so failures reproduce from the seed alone.
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    fn range(&mut self, low: u64, high: u64) -> u64 {
+        low + self.next() % (high - low + 1)
+    }
+}
+
+fn fuzz_create_packages(seed: u64) {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.cost_estimate().budget().reset_unlimited();
+
+    let admin = Address::generate(&env);
+    let recipients = [
+        Address::generate(&env),
+        Address::generate(&env),
+        Address::generate(&env),
+    ];
+    let token_admin = Address::generate(&env);
+    let (token_client, token_admin_client) = setup_token(&env, &token_admin);
+    let token = token_client.address.clone();
+
+    let contract_id = env.register(AidEscrow, ());
+    let client = AidEscrowClient::new(&env, &contract_id);
+    client.init(&admin);
+
+    let mut rng = Rng(seed);
+    let count = rng.range(50, 200) as usize;
+    let mut seen = BTreeSet::new();
+    let mut packages = Vec::new();
+    while packages.len() < count {
+        let id = rng.next();
+        if seen.insert(id) {
+            // Mix small amounts with ones near the top of the token's i64 range
+            let amount = if rng.next().is_multiple_of(10) {
+                rng.range(1 << 50, 1 << 52) as i128
+            } else {
+                rng.range(1, 1_000_000_000) as i128
+            };
+            packages.push((id, amount));
+        }
+    }
+    let total: i128 = packages.iter().map(|(_, amount)| amount).sum();
+
+    token_admin_client.mint(&admin, &total);
+    client.fund(&token, &admin, &total);
+
+    // Distinct IDs backed by exactly enough funds all succeed
+    for (i, (id, amount)) in packages.iter().enumerate() {
+        let recipient = &recipients[i % recipients.len()];
+        let res =
+            client.try_create_package(&admin, id, recipient, amount, &token, &0, &0, &None, &None);
+        assert!(
+            res.is_ok(),
+            "seed {seed}: create {id} ({amount}) failed: {res:?}"
+        );
+    }
+    assert_eq!(client.get_total_locked(&token), total);
+    assert_eq!(client.get_available_balance(&token), 0);
+    assert_eq!(client.get_package_count(), count as u64);
+
+    // Re-using any ID is rejected before solvency is even considered
+    for _ in 0..10 {
+        let (id, _) = packages[rng.range(0, count as u64 - 1) as usize];
+        assert_eq!(
+            client.try_create_package(
+                &admin,
+                &id,
+                &recipients[0],
+                &1,
+                &token,
+                &0,
+                &0,
+                &None,
+                &None
+            ),
+            Err(Ok(Error::PackageIdExists))
+        );
+    }
+
+    // The pool is fully committed: one more unit does not fit
+    let mut fresh = rng.next();
+    while seen.contains(&fresh) {
+        fresh = rng.next();
+    }
+    assert_eq!(
+        client.try_create_package(
+            &admin,
+            &fresh,
+            &recipients[0],
+            &1,
+            &token,
+            &0,
+            &0,
+            &None,
+            &None
+        ),
+        Err(Ok(Error::InsufficientFunds))
+    );
+    assert_eq!(client.get_total_locked(&token), total);
+}
+
+#[test]
+fn test_fuzz_create_package() {
+    for seed in [0x5eed, 0xdead_beef, 0x1234_5678_9abc_def1] {
+        fuzz_create_packages(seed);
+    }
+}
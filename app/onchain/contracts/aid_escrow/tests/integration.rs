@@ -2,11 +2,25 @@
 
 use aid_escrow::{AidEscrow, AidEscrowClient, Error, PackageStatus};
 use soroban_sdk::{
-    Address, Env,
+    Address, Env, contract, contractimpl,
     testutils::Address as _,
-    token::{StellarAssetClient, TokenClient},
+    token::{self, StellarAssetClient, TokenClient},
 };
 
+/// Stand-in for a DAO treasury that funds the escrow from its own balance.
+#[contract]
+pub struct MockTreasury;
+
+#[contractimpl]
+impl MockTreasury {
+    pub fn fund_escrow(env: Env, escrow: Address, token: Address, amount: i128) {
+        let this = env.current_contract_address();
+        let expiration = env.ledger().sequence() + 100;
+        token::Client::new(&env, &token).approve(&this, &escrow, &amount, &expiration);
+        AidEscrowClient::new(&env, &escrow).fund_from_contract(&token, &this, &amount);
+    }
+}
+
 fn setup_token(env: &Env, admin: &Address) -> (TokenClient<'static>, StellarAssetClient<'static>) {
     let token_contract = env.register_stellar_asset_contract_v2(admin.clone());
     let token_client = TokenClient::new(env, &token_contract.address());
@@ -44,11 +58,15 @@ fn test_integration_flow() {
     let expires_at = env.ledger().timestamp() + 86400; // 1 day from now
 
     let returned_id = client.create_package(
+        &admin,
         &pkg_id,
         &recipient,
         &1000,
         &token_client.address,
         &expires_at,
+        &0,
+        &None,
+        &None,
     );
     assert_eq!(returned_id, pkg_id);
 
@@ -99,8 +117,28 @@ fn test_multiple_packages() {
     let id2 = 101;
     let expiry = env.ledger().timestamp() + 86400;
 
-    client.create_package(&id1, &recipient1, &500, &token_client.address, &expiry);
-    client.create_package(&id2, &recipient2, &1000, &token_client.address, &expiry);
+    client.create_package(
+        &admin,
+        &id1,
+        &recipient1,
+        &500,
+        &token_client.address,
+        &expiry,
+        &0,
+        &None,
+        &None,
+    );
+    client.create_package(
+        &admin,
+        &id2,
+        &recipient2,
+        &1000,
+        &token_client.address,
+        &expiry,
+        &0,
+        &None,
+        &None,
+    );
 
     // Verify each package is independent
     let p1 = client.get_package(&id1);
@@ -137,12 +175,32 @@ fn test_error_cases() {
     client.fund(&token_client.address, &admin, &5000);
 
     // Test invalid amount (0)
-    let result = client.try_create_package(&0, &recipient, &0, &token_client.address, &86400);
+    let result = client.try_create_package(
+        &admin,
+        &0,
+        &recipient,
+        &0,
+        &token_client.address,
+        &86400,
+        &0,
+        &None,
+        &None,
+    );
     assert_eq!(result, Err(Ok(Error::InvalidAmount)));
 
     // Create valid package first to establish state
     let pkg_id = 1;
-    client.create_package(&pkg_id, &recipient, &1000, &token_client.address, &86400);
+    client.create_package(
+        &admin,
+        &pkg_id,
+        &recipient,
+        &1000,
+        &token_client.address,
+        &86400,
+        &0,
+        &None,
+        &None,
+    );
 
     // Try to claim non-existent package
     let result = client.try_claim(&999);
@@ -152,3 +210,105 @@ fn test_error_cases() {
     let result = client.try_get_package(&999);
     assert_eq!(result, Err(Ok(Error::PackageNotFound)));
 }
+
+#[test]
+fn test_fund_from_contract() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_client, token_admin_client) = setup_token(&env, &token_admin);
+    let token = token_client.address.clone();
+
+    let contract_id = env.register(AidEscrow, ());
+    let client = AidEscrowClient::new(&env, &contract_id);
+    client.init(&admin);
+
+    let treasury_id = env.register(MockTreasury, ());
+    let treasury = MockTreasuryClient::new(&env, &treasury_id);
+    token_admin_client.mint(&treasury_id, &1000);
+
+    // No mocked signatures: the treasury authorizes as the direct invoker
+    env.set_auths(&[]);
+    treasury.fund_escrow(&contract_id, &token, &600);
+
+    assert_eq!(token_client.balance(&treasury_id), 400);
+    assert_eq!(token_client.balance(&contract_id), 600);
+    assert_eq!(token_client.allowance(&treasury_id, &contract_id), 0);
+    assert_eq!(client.get_available_balance(&token), 600);
+
+    // Someone else cannot pull from the treasury, even with an open allowance
+    env.mock_all_auths();
+    token_client.approve(&treasury_id, &contract_id, &400, &100);
+    env.set_auths(&[]);
+    assert!(
+        client
+            .try_fund_from_contract(&token, &treasury_id, &400)
+            .is_err()
+    );
+    assert_eq!(token_client.balance(&treasury_id), 400);
+
+    env.mock_all_auths();
+    client.create_package(&admin, &1, &recipient, &600, &token, &0, &0, &None, &None);
+    client.claim(&1);
+    assert_eq!(token_client.balance(&recipient), 600);
+}
+
+#[test]
+fn test_transfer_package_to_contract() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_client, token_admin_client) = setup_token(&env, &token_admin);
+    let token = token_client.address.clone();
+
+    let source_id = env.register(AidEscrow, ());
+    let source = AidEscrowClient::new(&env, &source_id);
+    source.init(&admin);
+    let target_id = env.register(AidEscrow, ());
+    let target = AidEscrowClient::new(&env, &target_id);
+    target.init(&admin);
+
+    token_admin_client.mint(&admin, &1000);
+    source.fund(&token, &admin, &500);
+    target.fund(&token, &admin, &300);
+
+    source.create_package(&admin, &7, &recipient, &200, &token, &0, &0, &None, &None);
+    source.transfer_package_to_contract(&7, &target_id);
+
+    // Closed and unlocked in the source, and never refundable there...
+    assert_eq!(source.get_package(&7).status, PackageStatus::Transferred);
+    assert_eq!(source.get_total_locked(&token), 0);
+    assert_eq!(source.get_transferred_count(), 1);
+    assert_eq!(source.try_refund(&7), Err(Ok(Error::InvalidState)));
+    // ...re-created under the same ID and locked in the target
+    let moved = target.get_package(&7);
+    assert_eq!(moved.status, PackageStatus::Created);
+    assert_eq!(moved.recipient, recipient);
+    assert_eq!(moved.amount, 200);
+    assert_eq!(target.get_total_locked(&token), 200);
+
+    // No tokens move between contracts: each pool backs its own packages
+    target.claim(&7);
+    assert_eq!(token_client.balance(&recipient), 200);
+    assert_eq!(token_client.balance(&source_id), 500);
+    assert_eq!(token_client.balance(&target_id), 100);
+
+    // Already-terminal packages cannot be transferred
+    assert_eq!(
+        source.try_transfer_package_to_contract(&7, &target_id),
+        Err(Ok(Error::InvalidState))
+    );
+
+    // With auto-increment on, the target allocates its own ID instead of the source's
+    target.set_auto_increment_enabled(&true);
+    source.create_package(&admin, &8, &recipient, &50, &token, &0, &0, &None, &None);
+    source.transfer_package_to_contract(&8, &target_id);
+    assert_eq!(target.get_package(&1).amount, 50);
+    assert_eq!(target.try_get_package(&8), Err(Ok(Error::PackageNotFound)));
+}
@@ -0,0 +1,91 @@
+#![cfg(test)]
+
+use aid_escrow::{AidEscrow, AidEscrowClient, ComparisonOp, Error, OracleCondition};
+use soroban_sdk::{
+    Address, Env, Symbol, contract, contractimpl, symbol_short,
+    testutils::Address as _,
+    token::{StellarAssetClient, TokenClient},
+};
+
+#[contract]
+pub struct MockOracle;
+
+#[contractimpl]
+impl MockOracle {
+    pub fn set_value(env: Env, key: Symbol, value: i128) {
+        env.storage().instance().set(&key, &value);
+    }
+
+    pub fn get_value(env: Env, key: Symbol) -> i128 {
+        env.storage().instance().get(&key).unwrap_or(0)
+    }
+}
+
+fn setup_token(env: &Env, admin: &Address) -> (TokenClient<'static>, StellarAssetClient<'static>) {
+    let token_contract = env.register_stellar_asset_contract_v2(admin.clone());
+    let token_client = TokenClient::new(env, &token_contract.address());
+    let token_admin_client = StellarAssetClient::new(env, &token_contract.address());
+    (token_client, token_admin_client)
+}
+
+#[test]
+fn test_oracle_conditioned_claim() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_client, token_admin_client) = setup_token(&env, &token_admin);
+    let token = token_client.address.clone();
+
+    let oracle_id = env.register(MockOracle, ());
+    let oracle = MockOracleClient::new(&env, &oracle_id);
+    let rainfall = symbol_short!("rainfall");
+
+    let contract_id = env.register(AidEscrow, ());
+    let client = AidEscrowClient::new(&env, &contract_id);
+    client.init(&admin);
+
+    token_admin_client.mint(&admin, &1000);
+    client.fund(&token, &admin, &1000);
+
+    // Drought relief: released once rainfall drops below 10
+    let condition = OracleCondition {
+        oracle: oracle_id.clone(),
+        data_key: rainfall.clone(),
+        threshold: 10,
+        comparison: ComparisonOp::LessThan,
+    };
+    client.create_package(
+        &admin,
+        &1,
+        &recipient,
+        &500,
+        &token,
+        &0,
+        &0,
+        &None,
+        &Some(condition.clone()),
+    );
+    client.create_package(&admin, &2, &recipient, &100, &token, &0, &0, &None, &None);
+    assert_eq!(client.get_package_condition(&1), Some(condition));
+
+    oracle.set_value(&rainfall, &25);
+    assert!(!client.check_condition(&1));
+    assert_eq!(client.try_claim(&1), Err(Ok(Error::ConditionNotMet)));
+
+    // Unconditioned packages are unaffected
+    assert!(client.check_condition(&2));
+    client.claim(&2);
+
+    oracle.set_value(&rainfall, &5);
+    assert!(client.check_condition(&1));
+    client.claim(&1);
+    assert_eq!(token_client.balance(&recipient), 600);
+
+    assert_eq!(
+        client.try_check_condition(&99),
+        Err(Ok(Error::PackageNotFound))
+    );
+}
@@ -0,0 +1,1244 @@
+#![cfg(test)]
+
+use aid_escrow::{AidEscrow, AidEscrowClient, Error, PackageStatus};
+use soroban_sdk::{
+    Address, BytesN, Env, Map, String, Symbol,
+    testutils::{Address as _, EnvTestConfig, Ledger},
+    token::{StellarAssetClient, TokenClient},
+    vec,
+};
+
+fn setup_token(env: &Env, admin: &Address) -> (TokenClient<'static>, StellarAssetClient<'static>) {
+    let token_contract = env.register_stellar_asset_contract_v2(admin.clone());
+    let token_client = TokenClient::new(env, &token_contract.address());
+    let token_admin_client = StellarAssetClient::new(env, &token_contract.address());
+    (token_client, token_admin_client)
+}
+
+#[test]
+fn test_update_expiry() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_client, token_admin_client) = setup_token(&env, &token_admin);
+
+    let contract_id = env.register(AidEscrow, ());
+    let client = AidEscrowClient::new(&env, &contract_id);
+    client.init(&admin);
+
+    token_admin_client.mint(&admin, &1000);
+    client.fund(&token_client.address, &admin, &1000);
+
+    env.ledger().set_timestamp(1000);
+    client.create_package(
+        &admin,
+        &1,
+        &recipient,
+        &500,
+        &token_client.address,
+        &1100,
+        &0,
+        &None,
+        &None,
+    );
+
+    // Past or current timestamps are rejected
+    assert_eq!(
+        client.try_update_expiry(&1, &1000),
+        Err(Ok(Error::InvalidExpiry))
+    );
+
+    client.update_expiry(&1, &5000);
+    assert_eq!(client.get_package(&1).expires_at, 5000);
+
+    // Previously would have expired, now still claimable
+    env.ledger().set_timestamp(2000);
+    client.claim(&1);
+    assert_eq!(client.get_package(&1).status, PackageStatus::Claimed);
+
+    // Terminal packages cannot be updated
+    assert_eq!(
+        client.try_update_expiry(&1, &0),
+        Err(Ok(Error::InvalidState))
+    );
+}
+
+#[test]
+fn test_reassign_recipient() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let new_recipient = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_client, token_admin_client) = setup_token(&env, &token_admin);
+
+    let contract_id = env.register(AidEscrow, ());
+    let client = AidEscrowClient::new(&env, &contract_id);
+    client.init(&admin);
+
+    token_admin_client.mint(&admin, &1000);
+    client.fund(&token_client.address, &admin, &1000);
+    client.create_package(
+        &admin,
+        &1,
+        &recipient,
+        &500,
+        &token_client.address,
+        &0,
+        &0,
+        &None,
+        &None,
+    );
+
+    client.reassign_recipient(&1, &new_recipient);
+    let pkg = client.get_package(&1);
+    assert_eq!(pkg.recipient, new_recipient);
+    assert_eq!(pkg.amount, 500);
+
+    client.claim(&1);
+    assert_eq!(token_client.balance(&new_recipient), 500);
+    assert_eq!(token_client.balance(&recipient), 0);
+
+    assert_eq!(
+        client.try_reassign_recipient(&1, &recipient),
+        Err(Ok(Error::InvalidState))
+    );
+}
+
+#[test]
+fn test_metadata_entries() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_client, token_admin_client) = setup_token(&env, &token_admin);
+
+    let contract_id = env.register(AidEscrow, ());
+    let client = AidEscrowClient::new(&env, &contract_id);
+    client.init(&admin);
+
+    token_admin_client.mint(&admin, &1000);
+    client.fund(&token_client.address, &admin, &1000);
+    client.create_package(
+        &admin,
+        &1,
+        &recipient,
+        &500,
+        &token_client.address,
+        &0,
+        &0,
+        &None,
+        &None,
+    );
+
+    let key = Symbol::new(&env, "region");
+    assert_eq!(client.get_metadata_entry(&1, &key), None);
+
+    client.set_metadata_entry(&1, &key, &String::from_str(&env, "north"));
+    assert_eq!(
+        client.get_metadata_entry(&1, &key),
+        Some(String::from_str(&env, "north"))
+    );
+
+    client.set_metadata_entry(&1, &key, &String::from_str(&env, "south"));
+    assert_eq!(
+        client.get_package(&1).metadata.get(key.clone()),
+        Some(String::from_str(&env, "south"))
+    );
+
+    client.remove_metadata_entry(&1, &key);
+    assert_eq!(client.get_metadata_entry(&1, &key), None);
+
+    assert_eq!(
+        client.try_set_metadata_entry(&99, &key, &String::from_str(&env, "x")),
+        Err(Ok(Error::PackageNotFound))
+    );
+
+    client.revoke(&1);
+    assert_eq!(
+        client.try_set_metadata_entry(&1, &key, &String::from_str(&env, "x")),
+        Err(Ok(Error::InvalidState))
+    );
+    assert_eq!(
+        client.try_remove_metadata_entry(&1, &key),
+        Err(Ok(Error::InvalidState))
+    );
+}
+
+#[test]
+fn test_increase_package_amount() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_client, token_admin_client) = setup_token(&env, &token_admin);
+
+    let contract_id = env.register(AidEscrow, ());
+    let client = AidEscrowClient::new(&env, &contract_id);
+    client.init(&admin);
+
+    token_admin_client.mint(&admin, &1000);
+    client.fund(&token_client.address, &admin, &1000);
+    client.create_package(
+        &admin,
+        &1,
+        &recipient,
+        &500,
+        &token_client.address,
+        &0,
+        &0,
+        &None,
+        &None,
+    );
+
+    assert_eq!(
+        client.try_increase_package_amount(&1, &0),
+        Err(Ok(Error::InvalidAmount))
+    );
+    assert_eq!(
+        client.try_increase_package_amount(&1, &501),
+        Err(Ok(Error::InsufficientFunds))
+    );
+
+    // Max bound applies to the resulting amount
+    client.set_max_package_amount(&token_client.address, &800);
+    assert_eq!(
+        client.try_increase_package_amount(&1, &400),
+        Err(Ok(Error::AmountAboveMaximum))
+    );
+
+    client.increase_package_amount(&1, &300);
+    let pkg = client.get_package(&1);
+    assert_eq!(pkg.amount, 800);
+    assert_eq!(pkg.remaining_amount, 800);
+    assert_eq!(client.get_total_locked(&token_client.address), 800);
+
+    client.claim(&1);
+    assert_eq!(token_client.balance(&recipient), 800);
+}
+
+#[test]
+fn test_decrease_package_amount() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_client, token_admin_client) = setup_token(&env, &token_admin);
+
+    let contract_id = env.register(AidEscrow, ());
+    let client = AidEscrowClient::new(&env, &contract_id);
+    client.init(&admin);
+
+    token_admin_client.mint(&admin, &1000);
+    client.fund(&token_client.address, &admin, &1000);
+    client.create_package(
+        &admin,
+        &1,
+        &recipient,
+        &500,
+        &token_client.address,
+        &0,
+        &0,
+        &None,
+        &None,
+    );
+
+    // Reducing to zero must go through `revoke`
+    assert_eq!(
+        client.try_decrease_package_amount(&1, &500),
+        Err(Ok(Error::InvalidAmount))
+    );
+    assert_eq!(
+        client.try_decrease_package_amount(&1, &0),
+        Err(Ok(Error::InvalidAmount))
+    );
+
+    client.decrease_package_amount(&1, &200);
+    let pkg = client.get_package(&1);
+    assert_eq!(pkg.amount, 300);
+    assert_eq!(client.get_total_locked(&token_client.address), 300);
+    assert_eq!(client.get_available_balance(&token_client.address), 700);
+
+    client.claim(&1);
+    assert_eq!(token_client.balance(&recipient), 300);
+}
+
+#[test]
+fn test_clone_package() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let other = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_client, token_admin_client) = setup_token(&env, &token_admin);
+
+    let contract_id = env.register(AidEscrow, ());
+    let client = AidEscrowClient::new(&env, &contract_id);
+    client.init(&admin);
+
+    token_admin_client.mint(&admin, &1000);
+    client.fund(&token_client.address, &admin, &1000);
+
+    env.ledger().set_timestamp(100);
+    client.create_package(
+        &admin,
+        &1,
+        &recipient,
+        &500,
+        &token_client.address,
+        &9000,
+        &0,
+        &None,
+        &None,
+    );
+    let key = Symbol::new(&env, "program");
+    client.set_metadata_entry(&1, &key, &String::from_str(&env, "winter"));
+
+    // Cloning works from any state, including terminal ones
+    client.claim(&1);
+
+    assert_eq!(
+        client.try_clone_package(&99, &2, &other, &200),
+        Err(Ok(Error::PackageNotFound))
+    );
+
+    env.ledger().set_timestamp(200);
+    client.clone_package(&1, &2, &other, &200);
+
+    let clone = client.get_package(&2);
+    assert_eq!(clone.recipient, other);
+    assert_eq!(clone.amount, 200);
+    assert_eq!(clone.token, token_client.address);
+    assert_eq!(clone.expires_at, 9000);
+    assert_eq!(clone.created_at, 200);
+    assert_eq!(clone.status, PackageStatus::Created);
+    assert_eq!(
+        clone.metadata.get(key),
+        Some(String::from_str(&env, "winter"))
+    );
+    assert_eq!(client.get_total_locked(&token_client.address), 200);
+
+    // Solvency still applies: only 500 - 200 = 300 is free
+    assert_eq!(
+        client.try_clone_package(&1, &3, &other, &301),
+        Err(Ok(Error::InsufficientFunds))
+    );
+}
+
+#[test]
+fn test_package_templates() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_client, token_admin_client) = setup_token(&env, &token_admin);
+
+    let contract_id = env.register(AidEscrow, ());
+    let client = AidEscrowClient::new(&env, &contract_id);
+    client.init(&admin);
+
+    token_admin_client.mint(&admin, &1000);
+    client.fund(&token_client.address, &admin, &1000);
+
+    let mut metadata = Map::new(&env);
+    metadata.set(
+        Symbol::new(&env, "program"),
+        String::from_str(&env, "stipend"),
+    );
+    client.save_template(&7, &token_client.address, &250, &86400, &metadata);
+
+    let template = client.get_template(&7);
+    assert_eq!(template.amount, 250);
+    assert_eq!(template.metadata, metadata);
+
+    env.ledger().set_timestamp(1000);
+    client.create_package_from_template(&7, &1, &recipient);
+    client.create_package_from_template(&7, &2, &recipient);
+
+    let pkg = client.get_package(&2);
+    assert_eq!(pkg.amount, 250);
+    assert_eq!(pkg.token, token_client.address);
+    assert_eq!(pkg.expires_at, 1000 + 86400);
+    assert_eq!(pkg.metadata, metadata);
+    assert_eq!(client.get_total_locked(&token_client.address), 500);
+
+    client.delete_template(&7);
+    assert_eq!(
+        client.try_get_template(&7),
+        Err(Ok(Error::TemplateNotFound))
+    );
+    assert_eq!(
+        client.try_create_package_from_template(&7, &3, &recipient),
+        Err(Ok(Error::TemplateNotFound))
+    );
+}
+
+#[test]
+fn test_external_ref_lookup() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_client, token_admin_client) = setup_token(&env, &token_admin);
+
+    let contract_id = env.register(AidEscrow, ());
+    let client = AidEscrowClient::new(&env, &contract_id);
+    client.init(&admin);
+
+    token_admin_client.mint(&admin, &1000);
+    client.fund(&token_client.address, &admin, &1000);
+
+    let case_id = String::from_str(&env, "CASE-2024-0042");
+    client.create_package(
+        &admin,
+        &1,
+        &recipient,
+        &100,
+        &token_client.address,
+        &0,
+        &0,
+        &Some(case_id.clone()),
+        &None,
+    );
+    client.create_package(
+        &admin,
+        &2,
+        &recipient,
+        &100,
+        &token_client.address,
+        &0,
+        &0,
+        &None,
+        &None,
+    );
+
+    let package = client.get_package_by_external_ref(&case_id);
+    assert_eq!(package.id, 1);
+    assert_eq!(package.external_ref, case_id);
+    assert_eq!(
+        client.get_package(&2).external_ref,
+        String::from_str(&env, "")
+    );
+
+    // Refs are unique
+    assert_eq!(
+        client.try_create_package(
+            &admin,
+            &3,
+            &recipient,
+            &100,
+            &token_client.address,
+            &0,
+            &0,
+            &Some(case_id.clone()),
+            &None
+        ),
+        Err(Ok(Error::ExternalRefExists))
+    );
+
+    assert_eq!(
+        client.try_get_package_by_external_ref(&String::from_str(&env, "unknown")),
+        Err(Ok(Error::PackageNotFound))
+    );
+}
+
+#[test]
+fn test_split_package() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let parent = Address::generate(&env);
+    let child_a = Address::generate(&env);
+    let child_b = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_client, token_admin_client) = setup_token(&env, &token_admin);
+    let token = token_client.address.clone();
+
+    let contract_id = env.register(AidEscrow, ());
+    let client = AidEscrowClient::new(&env, &contract_id);
+    client.init(&admin);
+
+    token_admin_client.mint(&admin, &1000);
+    client.fund(&token, &admin, &1000);
+    client.create_package(&admin, &1, &parent, &600, &token, &5000, &0, &None, &None);
+
+    // Amounts must add up to the source amount
+    let uneven = vec![&env, (2, child_a.clone(), 300), (3, child_b.clone(), 200)];
+    assert_eq!(
+        client.try_split_package(&1, &uneven),
+        Err(Ok(Error::InvalidAmount))
+    );
+
+    // Overflowing or non-positive splits are rejected rather than panicking
+    let overflowing = vec![
+        &env,
+        (2, child_a.clone(), i128::MAX),
+        (3, child_b.clone(), i128::MAX),
+    ];
+    assert_eq!(
+        client.try_split_package(&1, &overflowing),
+        Err(Ok(Error::InvalidAmount))
+    );
+    let negative = vec![&env, (2, child_a.clone(), 700), (3, child_b.clone(), -100)];
+    assert_eq!(
+        client.try_split_package(&1, &negative),
+        Err(Ok(Error::InvalidAmount))
+    );
+
+    let splits = vec![
+        &env,
+        (2, parent.clone(), 200),
+        (3, child_a.clone(), 250),
+        (4, child_b.clone(), 150),
+    ];
+    let new_ids = client.split_package(&1, &splits);
+    assert_eq!(new_ids, vec![&env, 2, 3, 4]);
+
+    // The source is emptied: its funds now back the children and cannot be refunded
+    let source = client.get_package(&1);
+    assert_eq!(source.status, PackageStatus::Cancelled);
+    assert_eq!(source.remaining_amount, 0);
+    let child = client.get_package(&3);
+    assert_eq!(child.recipient, child_a);
+    assert_eq!(child.amount, 250);
+    assert_eq!(child.token, token);
+    assert_eq!(child.expires_at, 5000);
+    assert_eq!(client.get_total_locked(&token), 600);
+
+    // Only untouched packages can be split
+    client.partial_claim(&2, &50);
+    let splits = vec![&env, (5, parent.clone(), 200)];
+    assert_eq!(
+        client.try_split_package(&2, &splits),
+        Err(Ok(Error::InvalidState))
+    );
+}
+
+#[test]
+fn test_merge_packages() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let other = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_client, token_admin_client) = setup_token(&env, &token_admin);
+    let (other_token_client, other_token_admin_client) = setup_token(&env, &token_admin);
+    let token = token_client.address.clone();
+
+    let contract_id = env.register(AidEscrow, ());
+    let client = AidEscrowClient::new(&env, &contract_id);
+    client.init(&admin);
+
+    token_admin_client.mint(&admin, &1000);
+    client.fund(&token, &admin, &1000);
+    other_token_admin_client.mint(&admin, &1000);
+    client.fund(&other_token_client.address, &admin, &1000);
+
+    client.create_package(
+        &admin, &1, &recipient, &100, &token, &3000, &0, &None, &None,
+    );
+    client.create_package(
+        &admin, &2, &recipient, &200, &token, &5000, &0, &None, &None,
+    );
+    client.create_package(&admin, &3, &other, &50, &token, &0, &0, &None, &None);
+    client.create_package(
+        &admin,
+        &4,
+        &recipient,
+        &50,
+        &other_token_client.address,
+        &0,
+        &0,
+        &None,
+        &None,
+    );
+    client.set_metadata_entry(
+        &1,
+        &Symbol::new(&env, "region"),
+        &String::from_str(&env, "north"),
+    );
+    client.set_metadata_entry(
+        &2,
+        &Symbol::new(&env, "program"),
+        &String::from_str(&env, "food"),
+    );
+
+    assert_eq!(
+        client.try_merge_packages(&vec![&env, 1, 4], &10, &recipient),
+        Err(Ok(Error::TokenMismatch))
+    );
+    assert_eq!(
+        client.try_merge_packages(&vec![&env, 1, 3], &10, &recipient),
+        Err(Ok(Error::InvalidState))
+    );
+
+    client.merge_packages(&vec![&env, 1, 2], &10, &recipient);
+
+    let merged = client.get_package(&10);
+    assert_eq!(merged.amount, 300);
+    assert_eq!(merged.recipient, recipient);
+    assert_eq!(merged.expires_at, 5000);
+    assert_eq!(merged.metadata.len(), 2);
+    assert_eq!(client.get_package(&1).status, PackageStatus::Cancelled);
+    assert_eq!(client.get_package(&2).status, PackageStatus::Cancelled);
+    assert_eq!(client.get_total_locked(&token), 350);
+
+    // Cancelled sources cannot be merged again
+    assert_eq!(
+        client.try_merge_packages(&vec![&env, 1], &11, &recipient),
+        Err(Ok(Error::InvalidState))
+    );
+}
+
+#[test]
+fn test_package_categories() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_client, token_admin_client) = setup_token(&env, &token_admin);
+    let token = token_client.address.clone();
+
+    let contract_id = env.register(AidEscrow, ());
+    let client = AidEscrowClient::new(&env, &contract_id);
+    client.init(&admin);
+
+    token_admin_client.mint(&admin, &1000);
+    client.fund(&token, &admin, &1000);
+    for id in 1..=4u64 {
+        client.create_package(&admin, &id, &recipient, &100, &token, &0, &0, &None, &None);
+    }
+
+    let food = Symbol::new(&env, "food");
+    let medicine = Symbol::new(&env, "medicine");
+    client.set_package_category(&1, &food);
+    client.set_package_category(&2, &food);
+    client.set_package_category(&3, &food);
+    client.set_package_category(&4, &medicine);
+
+    assert_eq!(client.get_package_category(&1), Some(food.clone()));
+    assert_eq!(client.get_categories().len(), 2);
+    assert_eq!(
+        client.get_packages_by_category(&food, &0, &10),
+        vec![&env, 1, 2, 3]
+    );
+    assert_eq!(
+        client.get_packages_by_category(&food, &1, &1),
+        vec![&env, 2]
+    );
+    assert_eq!(client.get_packages_by_category(&food, &5, &10).len(), 0);
+
+    // Re-tagging moves the package
+    client.set_package_category(&3, &medicine);
+    assert_eq!(
+        client.get_packages_by_category(&medicine, &0, &10),
+        vec![&env, 4, 3]
+    );
+
+    // Terminal packages leave the index; empty categories disappear
+    client.claim(&1);
+    client.revoke(&2);
+    assert_eq!(client.get_packages_by_category(&food, &0, &10).len(), 0);
+    assert_eq!(client.get_categories(), vec![&env, medicine.clone()]);
+
+    assert_eq!(
+        client.try_set_package_category(&1, &food),
+        Err(Ok(Error::InvalidState))
+    );
+    assert_eq!(
+        client.try_set_package_category(&99, &food),
+        Err(Ok(Error::PackageNotFound))
+    );
+
+    // A locked package's category is frozen along with the rest of its metadata
+    client.lock_metadata(&4);
+    assert_eq!(
+        client.try_set_package_category(&4, &food),
+        Err(Ok(Error::MetadataLocked))
+    );
+    assert_eq!(client.get_package_category(&4), Some(medicine));
+}
+
+#[test]
+fn test_lock_metadata() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_client, token_admin_client) = setup_token(&env, &token_admin);
+    let token = token_client.address.clone();
+
+    let contract_id = env.register(AidEscrow, ());
+    let client = AidEscrowClient::new(&env, &contract_id);
+    client.init(&admin);
+
+    token_admin_client.mint(&admin, &1000);
+    client.fund(&token, &admin, &1000);
+    client.create_package(&admin, &1, &recipient, &100, &token, &0, &0, &None, &None);
+
+    let key = Symbol::new(&env, "case");
+    let value = String::from_str(&env, "C-42");
+    client.set_metadata_entry(&1, &key, &value);
+
+    assert!(!client.is_metadata_locked(&1));
+    client.lock_metadata(&1);
+    assert!(client.is_metadata_locked(&1));
+
+    assert_eq!(
+        client.try_set_metadata_entry(&1, &key, &String::from_str(&env, "C-43")),
+        Err(Ok(Error::MetadataLocked))
+    );
+    assert_eq!(
+        client.try_remove_metadata_entry(&1, &key),
+        Err(Ok(Error::MetadataLocked))
+    );
+
+    // Metadata is still readable and unchanged
+    assert_eq!(client.get_package(&1).metadata.get(key), Some(value));
+    assert_eq!(
+        client.try_lock_metadata(&99),
+        Err(Ok(Error::PackageNotFound))
+    );
+}
+
+#[test]
+fn test_package_priority() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_client, token_admin_client) = setup_token(&env, &token_admin);
+    let token = token_client.address.clone();
+
+    let contract_id = env.register(AidEscrow, ());
+    let client = AidEscrowClient::new(&env, &contract_id);
+    client.init(&admin);
+
+    token_admin_client.mint(&admin, &1000);
+    client.fund(&token, &admin, &1000);
+    for id in 1..=4u64 {
+        client.create_package(&admin, &id, &recipient, &100, &token, &0, &0, &None, &None);
+    }
+
+    // Everything defaults to normal priority, which is still listed at level 0
+    assert_eq!(client.get_package(&1).priority, 0);
+    assert_eq!(
+        client.get_packages_by_priority(&0, &0, &10),
+        vec![&env, 1, 2, 3, 4]
+    );
+    assert!(client.get_packages_by_priority(&1, &0, &10).is_empty());
+
+    client.set_package_priority(&3, &255);
+    client.set_package_priority(&2, &100);
+    client.set_package_priority(&4, &100);
+    assert_eq!(client.get_package(&3).priority, 255);
+
+    assert_eq!(
+        client.get_packages_by_priority(&0, &0, &10),
+        vec![&env, 3, 2, 4, 1]
+    );
+    assert_eq!(
+        client.get_packages_by_priority(&100, &0, &10),
+        vec![&env, 3, 2, 4]
+    );
+    assert_eq!(
+        client.get_packages_by_priority(&0, &1, &2),
+        vec![&env, 2, 4]
+    );
+
+    // Terminal and deprioritized packages drop out of the listing
+    client.claim(&3);
+    client.set_package_priority(&2, &0);
+    assert_eq!(
+        client.get_packages_by_priority(&100, &0, &10),
+        vec![&env, 4]
+    );
+    assert_eq!(
+        client.get_packages_by_priority(&0, &0, &10),
+        vec![&env, 4, 1, 2]
+    );
+
+    assert_eq!(
+        client.try_set_package_priority(&1, &256),
+        Err(Ok(Error::InvalidPriority))
+    );
+    assert_eq!(
+        client.try_set_package_priority(&3, &1),
+        Err(Ok(Error::InvalidState))
+    );
+}
+
+#[test]
+fn test_create_package_idempotent() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_client, token_admin_client) = setup_token(&env, &token_admin);
+    let token = token_client.address.clone();
+
+    let contract_id = env.register(AidEscrow, ());
+    let client = AidEscrowClient::new(&env, &contract_id);
+    client.init(&admin);
+
+    token_admin_client.mint(&admin, &1000);
+    client.fund(&token, &admin, &1000);
+
+    let nonce = BytesN::from_array(&env, &[1; 32]);
+    assert_eq!(
+        client.create_package_idempotent(&nonce, &1, &recipient, &100, &token, &0),
+        1
+    );
+
+    // A retried submission is a no-op that reports the original package
+    assert_eq!(
+        client.create_package_idempotent(&nonce, &1, &recipient, &100, &token, &0),
+        1
+    );
+    assert_eq!(
+        client.create_package_idempotent(&nonce, &2, &recipient, &100, &token, &0),
+        1
+    );
+    assert_eq!(client.get_package_count(), 1);
+    assert_eq!(client.get_total_locked(&token), 100);
+    assert!(client.try_get_package(&2).is_err());
+
+    // A fresh nonce still hits the usual ID uniqueness check
+    let other = BytesN::from_array(&env, &[2; 32]);
+    assert_eq!(
+        client.try_create_package_idempotent(&other, &1, &recipient, &100, &token, &0),
+        Err(Ok(Error::PackageIdExists))
+    );
+}
+
+#[test]
+fn test_packages_for_recipient() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_client, token_admin_client) = setup_token(&env, &token_admin);
+    let token = token_client.address.clone();
+
+    let contract_id = env.register(AidEscrow, ());
+    let client = AidEscrowClient::new(&env, &contract_id);
+    client.init(&admin);
+
+    token_admin_client.mint(&admin, &1000);
+    client.fund(&token, &admin, &1000);
+
+    assert_eq!(client.get_recipient_package_count(&alice), 0);
+    assert_eq!(client.get_packages_for_recipient(&alice, &0, &10).len(), 0);
+
+    for id in 1..=5u64 {
+        client.create_package(&admin, &id, &alice, &100, &token, &0, &0, &None, &None);
+    }
+    client.create_package(&admin, &6, &bob, &100, &token, &0, &0, &None, &None);
+
+    // Terminal packages stay listed as history
+    client.claim(&1);
+    assert_eq!(client.get_recipient_package_count(&alice), 5);
+    assert_eq!(
+        client.get_packages_for_recipient(&alice, &0, &10),
+        vec![&env, 1, 2, 3, 4, 5]
+    );
+    assert_eq!(
+        client.get_packages_for_recipient(&alice, &1, &2),
+        vec![&env, 2, 3]
+    );
+    assert_eq!(client.get_packages_for_recipient(&alice, &5, &2).len(), 0);
+
+    // Reassignment lists the package under the new recipient too
+    client.reassign_recipient(&2, &bob);
+    assert_eq!(
+        client.get_packages_for_recipient(&bob, &0, &10),
+        vec![&env, 6, 2]
+    );
+}
+
+#[test]
+fn test_all_package_ids_paginated() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_client, token_admin_client) = setup_token(&env, &token_admin);
+    let token = token_client.address.clone();
+
+    let contract_id = env.register(AidEscrow, ());
+    let client = AidEscrowClient::new(&env, &contract_id);
+    client.init(&admin);
+
+    assert_eq!(client.get_total_package_count(), 0);
+    assert_eq!(client.get_all_package_ids_paginated(&0, &10).len(), 0);
+
+    token_admin_client.mint(&admin, &1000);
+    client.fund(&token, &admin, &1000);
+    for id in [30u64, 10, 20, 50, 40] {
+        client.create_package(&admin, &id, &recipient, &10, &token, &0, &0, &None, &None);
+    }
+    client.claim(&10);
+
+    // Creation order, terminal packages included
+    assert_eq!(client.get_total_package_count(), 5);
+    assert_eq!(
+        client.get_all_package_ids_paginated(&0, &5),
+        vec![&env, 30, 10, 20, 50, 40]
+    );
+    assert_eq!(
+        client.get_all_package_ids_paginated(&1, &2),
+        vec![&env, 10, 20]
+    );
+
+    // Limit past the end returns only what exists; offset past the end returns nothing
+    assert_eq!(
+        client.get_all_package_ids_paginated(&3, &100),
+        vec![&env, 50, 40]
+    );
+    assert_eq!(client.get_all_package_ids_paginated(&5, &10).len(), 0);
+    assert_eq!(client.get_all_package_ids_paginated(&99, &10).len(), 0);
+    assert_eq!(client.get_all_package_ids_paginated(&0, &0).len(), 0);
+}
+
+#[test]
+fn test_all_package_ids_across_pages() {
+    // Hundreds of creations would make the recorded snapshot enormous
+    let env = Env::new_with_config(EnvTestConfig {
+        capture_snapshot_at_drop: false,
+    });
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_client, token_admin_client) = setup_token(&env, &token_admin);
+    let token = token_client.address.clone();
+
+    let contract_id = env.register(AidEscrow, ());
+    let client = AidEscrowClient::new(&env, &contract_id);
+    client.init(&admin);
+
+    token_admin_client.mint(&admin, &1000);
+    client.fund(&token, &admin, &1000);
+    for id in 1..=502u64 {
+        client.create_package(&admin, &id, &recipient, &1, &token, &0, &0, &None, &None);
+    }
+
+    // IDs are stored 500 to a page; reads spanning the boundary stay in order
+    assert_eq!(client.get_total_package_count(), 502);
+    assert_eq!(
+        client.get_all_package_ids_paginated(&498, &10),
+        vec![&env, 499, 500, 501, 502]
+    );
+    assert_eq!(
+        client.get_all_package_ids_paginated(&500, &1),
+        vec![&env, 501]
+    );
+    assert_eq!(client.get_recipient_package_count(&recipient), 502);
+    assert_eq!(
+        client.get_packages_for_recipient(&recipient, &499, &2),
+        vec![&env, 500, 501]
+    );
+}
+
+#[test]
+fn test_bulk_extend_expiry() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_client, token_admin_client) = setup_token(&env, &token_admin);
+    let token = token_client.address.clone();
+
+    let contract_id = env.register(AidEscrow, ());
+    let client = AidEscrowClient::new(&env, &contract_id);
+    client.init(&admin);
+
+    token_admin_client.mint(&admin, &1000);
+    client.fund(&token, &admin, &1000);
+
+    env.ledger().set_timestamp(1000);
+    for id in 1..=4u64 {
+        client.create_package(
+            &admin, &id, &recipient, &100, &token, &2000, &0, &None, &None,
+        );
+    }
+    client.partial_claim(&2, &50);
+    client.revoke(&3);
+
+    assert_eq!(
+        client.try_bulk_extend_expiry(&vec![&env, 1, 4], &1000),
+        Err(Ok(Error::InvalidExpiry))
+    );
+
+    // Only untouched `Created` packages move; others and unknown IDs are skipped
+    let updated = client.bulk_extend_expiry(&vec![&env, 1, 2, 3, 4, 99], &5000);
+    assert_eq!(updated, 2);
+    assert_eq!(client.get_package(&1).expires_at, 5000);
+    assert_eq!(client.get_package(&4).expires_at, 5000);
+    assert_eq!(client.get_package(&2).expires_at, 2000);
+    assert_eq!(client.get_package(&3).expires_at, 2000);
+
+    client.set_max_expiry_duration(&3600);
+    assert_eq!(
+        client.try_bulk_extend_expiry(&vec![&env, 1], &4601),
+        Err(Ok(Error::ExpiryTooLong))
+    );
+    assert_eq!(client.bulk_extend_expiry(&vec![&env, 1], &4600), 1);
+}
+
+#[test]
+fn test_package_note() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_client, token_admin_client) = setup_token(&env, &token_admin);
+    let token = token_client.address.clone();
+
+    let contract_id = env.register(AidEscrow, ());
+    let client = AidEscrowClient::new(&env, &contract_id);
+    client.init(&admin);
+
+    token_admin_client.mint(&admin, &1000);
+    client.fund(&token, &admin, &1000);
+    client.create_package(&admin, &1, &recipient, &100, &token, &0, &0, &None, &None);
+
+    assert_eq!(client.get_package_note(&1), None);
+    assert_eq!(
+        client.try_set_package_note(&2, &String::from_str(&env, "x")),
+        Err(Ok(Error::PackageNotFound))
+    );
+
+    let first = String::from_str(&env, "recipient hospitalized, contact field office");
+    client.set_package_note(&1, &first);
+    assert_eq!(client.get_package_note(&1), Some(first));
+
+    // Notes can be overwritten, including after the package is claimed
+    client.claim(&1);
+    let second = String::from_str(&env, "delivered by field office");
+    client.set_package_note(&1, &second);
+    assert_eq!(client.get_package_note(&1), Some(second.clone()));
+
+    client.lock_metadata(&1);
+    assert_eq!(
+        client.try_set_package_note(&1, &String::from_str(&env, "late edit")),
+        Err(Ok(Error::MetadataLocked))
+    );
+    assert_eq!(client.get_package_note(&1), Some(second));
+}
+
+#[test]
+fn test_metadata_round_trip() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_client, token_admin_client) = setup_token(&env, &token_admin);
+    let token = token_client.address.clone();
+
+    let contract_id = env.register(AidEscrow, ());
+    let client = AidEscrowClient::new(&env, &contract_id);
+    client.init(&admin);
+
+    token_admin_client.mint(&admin, &1000);
+    client.fund(&token, &admin, &1000);
+
+    // `create_package` always starts from an empty map
+    client.create_package(&admin, &1, &recipient, &100, &token, &0, &0, &None, &None);
+    assert!(client.get_package(&1).metadata.is_empty());
+
+    // Keys from 1 to 32 characters, values with multi-byte UTF-8
+    let entries = [
+        ("r", "北部地区"),
+        ("region", "Région du Nord"),
+        ("program_code", "ПРОГРАММА-7"),
+        ("household_size_cat", "4–6 people 👨‍👩‍👧‍👦"),
+        ("a_thirty_two_character_key_name_", "مساعدات غذائية"),
+    ];
+    let mut metadata: Map<Symbol, String> = Map::new(&env);
+    for (key, value) in entries {
+        metadata.set(Symbol::new(&env, key), String::from_str(&env, value));
+    }
+    client.save_template(&1, &token, &100, &0, &metadata);
+    client.create_package_from_template(&1, &2, &recipient);
+
+    let stored = client.get_package(&2).metadata;
+    assert_eq!(stored.len(), entries.len() as u32);
+    assert_eq!(stored, metadata);
+    for (key, value) in entries {
+        assert_eq!(
+            client.get_metadata_entry(&2, &Symbol::new(&env, key)),
+            Some(String::from_str(&env, value))
+        );
+    }
+
+    // Mutation and deletion touch only the targeted entry
+    let region = Symbol::new(&env, "region");
+    client.set_metadata_entry(&2, &region, &String::from_str(&env, "Région du Sud"));
+    client.remove_metadata_entry(&2, &Symbol::new(&env, "r"));
+
+    let mut expected = metadata.clone();
+    expected.set(region, String::from_str(&env, "Région du Sud"));
+    expected.remove(Symbol::new(&env, "r"));
+    assert_eq!(client.get_package(&2).metadata, expected);
+    assert_eq!(client.get_package(&2).metadata.len(), 4);
+}
+
+#[test]
+fn test_recipient_active_index() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let carol = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_client, token_admin_client) = setup_token(&env, &token_admin);
+    let token = token_client.address.clone();
+
+    let contract_id = env.register(AidEscrow, ());
+    let client = AidEscrowClient::new(&env, &contract_id);
+    client.init(&admin);
+
+    token_admin_client.mint(&admin, &10_000);
+    client.fund(&token, &admin, &10_000);
+
+    for (id, recipient) in [
+        (1, &alice),
+        (2, &bob),
+        (3, &alice),
+        (4, &bob),
+        (5, &alice),
+        (6, &bob),
+    ] {
+        client.create_package(&admin, &id, recipient, &100, &token, &0, &0, &None, &None);
+    }
+    assert_eq!(
+        client.get_recipient_active_pkgs(&alice),
+        vec![&env, 1, 3, 5]
+    );
+    assert_eq!(client.get_recipient_active_pkgs(&bob), vec![&env, 2, 4, 6]);
+    assert_eq!(client.get_recipient_active_pkgs(&carol), vec![&env]);
+
+    // Partial claims keep a package active; full claims, disbursements and revokes drop it
+    client.partial_claim(&1, &40);
+    client.claim(&3);
+    client.revoke(&4);
+    client.disburse(&6);
+    assert_eq!(client.get_recipient_active_pkgs(&alice), vec![&env, 1, 5]);
+    assert_eq!(client.get_recipient_active_pkgs(&bob), vec![&env, 2]);
+
+    // Reassignment moves the ID between recipients
+    client.reassign_recipient(&5, &carol);
+    assert_eq!(client.get_recipient_active_pkgs(&alice), vec![&env, 1]);
+    assert_eq!(client.get_recipient_active_pkgs(&carol), vec![&env, 5]);
+
+    client.claim(&1);
+    client.revoke(&2);
+    assert_eq!(client.get_recipient_active_pkgs(&alice), vec![&env]);
+    assert_eq!(client.get_recipient_active_pkgs(&bob), vec![&env]);
+
+    for recipient in [&alice, &bob, &carol] {
+        assert_eq!(
+            client.get_recipient_active_pkgs(recipient).len(),
+            client.get_recipient_active_pkg_count(recipient)
+        );
+    }
+}
+
+#[test]
+fn test_packages_by_token() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_a, token_a_admin) = setup_token(&env, &token_admin);
+    let (token_b, token_b_admin) = setup_token(&env, &token_admin);
+
+    let contract_id = env.register(AidEscrow, ());
+    let client = AidEscrowClient::new(&env, &contract_id);
+    client.init(&admin);
+
+    token_a_admin.mint(&admin, &1000);
+    token_b_admin.mint(&admin, &1000);
+    client.fund(&token_a.address, &admin, &1000);
+    client.fund(&token_b.address, &admin, &1000);
+
+    // Interleave the tokens: odd IDs in token_a, even IDs in token_b
+    for id in 1..=10u64 {
+        let token = if id % 2 == 1 {
+            &token_a.address
+        } else {
+            &token_b.address
+        };
+        client.create_package(&admin, &id, &recipient, &100, token, &0, &0, &None, &None);
+    }
+    // Terminal packages stay listed
+    client.claim(&1);
+
+    assert_eq!(client.get_package_count_for_token(&token_a.address), 5);
+    assert_eq!(client.get_package_count_for_token(&token_b.address), 5);
+    let a_ids = client.get_packages_by_token(&token_a.address, &0, &10);
+    let b_ids = client.get_packages_by_token(&token_b.address, &0, &10);
+    assert_eq!(a_ids, vec![&env, 1, 3, 5, 7, 9]);
+    assert_eq!(b_ids, vec![&env, 2, 4, 6, 8, 10]);
+    for id in a_ids.iter() {
+        assert!(!b_ids.contains(id));
+    }
+
+    assert_eq!(
+        client.get_packages_by_token(&token_a.address, &3, &10),
+        vec![&env, 7, 9]
+    );
+    assert_eq!(
+        client
+            .get_packages_by_token(&token_b.address, &5, &10)
+            .len(),
+        0
+    );
+    let unused = Address::generate(&env);
+    assert_eq!(client.get_package_count_for_token(&unused), 0);
+}
@@ -0,0 +1,275 @@
+#![cfg(test)]
+
+use aid_escrow::{AidEscrow, AidEscrowClient, Error, PackageStatus};
+use soroban_sdk::{
+    Address, Env, String,
+    testutils::{Address as _, Ledger},
+    token::{StellarAssetClient, TokenClient},
+    vec,
+};
+
+fn setup_token(env: &Env, admin: &Address) -> (TokenClient<'static>, StellarAssetClient<'static>) {
+    let token_contract = env.register_stellar_asset_contract_v2(admin.clone());
+    let token_client = TokenClient::new(env, &token_contract.address());
+    let token_admin_client = StellarAssetClient::new(env, &token_contract.address());
+    (token_client, token_admin_client)
+}
+
+#[test]
+fn test_vesting_schedule() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_client, token_admin_client) = setup_token(&env, &token_admin);
+    let token = token_client.address.clone();
+
+    let contract_id = env.register(AidEscrow, ());
+    let client = AidEscrowClient::new(&env, &contract_id);
+    client.init(&admin);
+
+    token_admin_client.mint(&admin, &10_000);
+    client.fund(&token, &admin, &10_000);
+
+    env.ledger().set_timestamp(1000);
+    assert_eq!(
+        client.try_create_vesting_package(&1, &recipient, &1000, &token, &2000, &1000, &0),
+        Err(Ok(Error::InvalidSchedule))
+    );
+    assert_eq!(
+        client.try_create_vesting_package(&1, &recipient, &1000, &token, &1000, &2000, &3000),
+        Err(Ok(Error::InvalidSchedule))
+    );
+
+    // Vests from 1000 to 2000 with a cliff at 1250
+    client.create_vesting_package(&1, &recipient, &1000, &token, &1000, &2000, &1250);
+    assert_eq!(client.get_total_locked(&token), 1000);
+
+    // Before the cliff nothing is vested
+    env.ledger().set_timestamp(1200);
+    assert_eq!(client.get_vested_amount(&1), 0);
+    assert_eq!(
+        client.try_claim_vested(&1),
+        Err(Ok(Error::PackageNotYetClaimable))
+    );
+
+    // At the cliff the linear amount since start_at becomes available
+    env.ledger().set_timestamp(1250);
+    assert_eq!(client.get_vested_amount(&1), 250);
+    assert_eq!(client.claim_vested(&1), 250);
+    assert_eq!(token_client.balance(&recipient), 250);
+    assert_eq!(
+        client.get_package(&1).status,
+        PackageStatus::PartiallyClaimed
+    );
+
+    // Plain claims cannot take unvested funds
+    env.ledger().set_timestamp(1500);
+    assert_eq!(client.try_claim(&1), Err(Ok(Error::InvalidAmount)));
+    assert_eq!(client.claim_vested(&1), 250);
+    assert_eq!(client.try_claim_vested(&1), Err(Ok(Error::InvalidAmount)));
+
+    // Past end_at everything is vested
+    env.ledger().set_timestamp(5000);
+    assert_eq!(client.get_vested_amount(&1), 1000);
+    assert_eq!(client.claim_vested(&1), 500);
+    assert_eq!(token_client.balance(&recipient), 1000);
+    assert_eq!(client.get_package(&1).status, PackageStatus::Claimed);
+    assert_eq!(
+        client.get_vesting_schedule(&1).unwrap().claimed_amount,
+        1000
+    );
+    assert_eq!(client.get_total_locked(&token), 0);
+}
+
+#[test]
+fn test_milestone_package() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_client, token_admin_client) = setup_token(&env, &token_admin);
+    let token = token_client.address.clone();
+
+    let contract_id = env.register(AidEscrow, ());
+    let client = AidEscrowClient::new(&env, &contract_id);
+    client.init(&admin);
+
+    token_admin_client.mint(&admin, &10_000);
+    client.fund(&token, &admin, &10_000);
+
+    let milestones = vec![
+        &env,
+        (300, String::from_str(&env, "Site survey")),
+        (700, String::from_str(&env, "Construction")),
+    ];
+    client.create_package_with_milestones(&1, &recipient, &token, &milestones, &0);
+    assert_eq!(client.get_package(&1).amount, 1000);
+    assert_eq!(client.get_total_locked(&token), 1000);
+
+    // Nothing can be claimed until the admin releases a stage
+    assert_eq!(
+        client.try_claim_milestone(&1, &0),
+        Err(Ok(Error::InvalidState))
+    );
+    assert_eq!(client.try_claim(&1), Err(Ok(Error::InvalidAmount)));
+    assert_eq!(
+        client.try_release_milestone(&1, &5),
+        Err(Ok(Error::MilestoneNotFound))
+    );
+
+    client.release_milestone(&1, &0);
+    assert_eq!(
+        client.try_release_milestone(&1, &0),
+        Err(Ok(Error::InvalidState))
+    );
+    client.claim_milestone(&1, &0);
+    assert_eq!(token_client.balance(&recipient), 300);
+    assert_eq!(
+        client.get_package(&1).status,
+        PackageStatus::PartiallyClaimed
+    );
+    assert_eq!(
+        client.try_claim_milestone(&1, &0),
+        Err(Ok(Error::InvalidState))
+    );
+
+    client.release_milestone(&1, &1);
+    client.claim_milestone(&1, &1);
+    assert_eq!(token_client.balance(&recipient), 1000);
+    assert_eq!(client.get_package(&1).status, PackageStatus::Claimed);
+
+    let stages = client.get_milestones(&1);
+    assert_eq!(stages.len(), 2);
+    assert!(stages.iter().all(|m| m.released && m.claimed));
+    assert_eq!(client.get_total_locked(&token), 0);
+}
+
+#[test]
+fn test_recurring_package() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_client, token_admin_client) = setup_token(&env, &token_admin);
+    let token = token_client.address.clone();
+
+    let contract_id = env.register(AidEscrow, ());
+    let client = AidEscrowClient::new(&env, &contract_id);
+    client.init(&admin);
+
+    token_admin_client.mint(&admin, &1000);
+    client.fund(&token, &admin, &1000);
+
+    env.ledger().set_timestamp(1000);
+    client.create_package(&admin, &1, &recipient, &100, &token, &0, &0, &None, &None);
+    client.set_recurring(&1, &2_592_000, &2);
+    assert_eq!(client.get_recurring_config(&1).unwrap().recurrence_count, 0);
+
+    // First claim: ID 2 is the next free ID
+    client.claim(&1);
+    let second = client.get_package(&2);
+    assert_eq!(second.recipient, recipient);
+    assert_eq!(second.amount, 100);
+    assert_eq!(second.expires_at, 1000 + 2_592_000);
+    assert_eq!(client.get_recurring_config(&2).unwrap().recurrence_count, 1);
+
+    // Admin-assigned IDs are skipped
+    client.create_package(&admin, &3, &recipient, &50, &token, &0, &0, &None, &None);
+    client.claim(&2);
+    assert_eq!(client.get_recurring_config(&4).unwrap().recurrence_count, 2);
+
+    // max_recurrences reached: the cycle ends
+    client.claim(&4);
+    assert_eq!(client.try_get_package(&5), Err(Ok(Error::PackageNotFound)));
+    assert_eq!(token_client.balance(&recipient), 300);
+    assert_eq!(client.get_total_locked(&token), 50);
+}
+
+#[test]
+fn test_cancel_recurrence() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_client, token_admin_client) = setup_token(&env, &token_admin);
+    let token = token_client.address.clone();
+
+    let contract_id = env.register(AidEscrow, ());
+    let client = AidEscrowClient::new(&env, &contract_id);
+    client.init(&admin);
+
+    token_admin_client.mint(&admin, &1000);
+    client.fund(&token, &admin, &1000);
+
+    client.create_package(&admin, &1, &recipient, &100, &token, &0, &0, &None, &None);
+    client.set_recurring(&1, &86400, &10);
+    client.cancel_recurrence(&1);
+    assert_eq!(client.get_recurring_config(&1), None);
+    assert_eq!(
+        client.try_cancel_recurrence(&1),
+        Err(Ok(Error::InvalidState))
+    );
+
+    client.claim(&1);
+    assert_eq!(client.get_package_count(), 1);
+}
+
+#[test]
+fn test_convert_to_recurring() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_client, token_admin_client) = setup_token(&env, &token_admin);
+    let token = token_client.address.clone();
+
+    let contract_id = env.register(AidEscrow, ());
+    let client = AidEscrowClient::new(&env, &contract_id);
+    client.init(&admin);
+
+    token_admin_client.mint(&admin, &1000);
+    client.fund(&token, &admin, &1000);
+
+    env.ledger().set_timestamp(1000);
+    client.create_package(&admin, &1, &recipient, &100, &token, &0, &0, &None, &None);
+    client.create_package(&admin, &2, &recipient, &100, &token, &0, &0, &None, &None);
+
+    client.convert_to_recurring(&1, &86400, &1);
+    assert_eq!(
+        client.try_convert_to_recurring(&1, &3600, &5),
+        Err(Ok(Error::AlreadyRecurring))
+    );
+    assert_eq!(
+        client.get_recurring_config(&1).unwrap().interval_seconds,
+        86400
+    );
+
+    // Claiming honors the converted config
+    client.claim(&1);
+    assert_eq!(client.get_package(&3).expires_at, 1000 + 86400);
+
+    // Only unclaimed packages can be converted
+    assert_eq!(
+        client.try_convert_to_recurring(&1, &86400, &1),
+        Err(Ok(Error::InvalidState))
+    );
+
+    // A removed conversion no longer re-creates the package
+    client.convert_to_recurring(&2, &86400, &1);
+    client.remove_recurrence(&2);
+    assert_eq!(client.get_recurring_config(&2), None);
+    client.claim(&2);
+    assert_eq!(client.try_get_package(&4), Err(Ok(Error::PackageNotFound)));
+    assert_eq!(token_client.balance(&recipient), 200);
+}
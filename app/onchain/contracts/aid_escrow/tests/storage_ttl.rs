@@ -0,0 +1,172 @@
+#![cfg(test)]
+
+use aid_escrow::{AidEscrow, AidEscrowClient, Error};
+use soroban_sdk::{
+    Address, Env, symbol_short,
+    testutils::{
+        Address as _, Ledger,
+        storage::{Instance as _, Persistent as _},
+    },
+    token::{StellarAssetClient, TokenClient},
+};
+
+fn setup_token(env: &Env, admin: &Address) -> (TokenClient<'static>, StellarAssetClient<'static>) {
+    let token_contract = env.register_stellar_asset_contract_v2(admin.clone());
+    let token_client = TokenClient::new(env, &token_contract.address());
+    let token_admin_client = StellarAssetClient::new(env, &token_contract.address());
+    (token_client, token_admin_client)
+}
+
+#[test]
+fn test_package_ttl_extension() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_client, token_admin_client) = setup_token(&env, &token_admin);
+
+    let contract_id = env.register(AidEscrow, ());
+    let client = AidEscrowClient::new(&env, &contract_id);
+    client.init(&admin);
+
+    token_admin_client.mint(&admin, &1000);
+    client.fund(&token_client.address, &admin, &1000);
+
+    // Package 1 gets an explicit TTL, package 2 keeps the network default
+    client.create_package(
+        &admin,
+        &1,
+        &recipient,
+        &100,
+        &token_client.address,
+        &0,
+        &0,
+        &None,
+        &None,
+    );
+    client.create_package(
+        &admin,
+        &2,
+        &recipient,
+        &100,
+        &token_client.address,
+        &0,
+        &0,
+        &None,
+        &None,
+    );
+    assert_eq!(client.get_package_ttl(&1), 0);
+
+    let max_ttl = env.as_contract(&contract_id, || env.storage().max_ttl());
+    assert_eq!(
+        client.try_extend_package_ttl(&1, &0),
+        Err(Ok(Error::InvalidTtlExtension))
+    );
+    assert_eq!(
+        client.try_extend_package_ttl(&1, &(max_ttl + 1)),
+        Err(Ok(Error::InvalidTtlExtension))
+    );
+
+    client.extend_package_ttl(&1, &50_000);
+    assert_eq!(client.get_package_ttl(&1), 50_000);
+    // A shorter extension does not shorten the recorded lifetime
+    client.extend_package_ttl(&1, &10_000);
+    assert_eq!(client.get_package_ttl(&1), 50_000);
+
+    let (ttl, default_ttl) = env.as_contract(&contract_id, || {
+        let storage = env.storage().persistent();
+        (
+            storage.get_ttl(&(symbol_short!("pkg"), 1u64)),
+            storage.get_ttl(&(symbol_short!("pkg"), 2u64)),
+        )
+    });
+    assert_eq!(ttl, 50_000);
+    // Package 2 was never extended and would be archived before the ledger below
+    // (the test host transparently restores archived entries, so this is checked up front)
+    assert!(default_ttl < 20_000);
+
+    // Keep the contract instance itself alive while the ledger advances
+    env.as_contract(&contract_id, || {
+        env.storage().instance().extend_ttl(100_000, 100_000)
+    });
+    env.ledger().with_mut(|li| li.sequence_number += 20_000);
+
+    assert_eq!(client.get_package_ttl(&1), 30_000);
+    assert_eq!(client.get_package(&1).amount, 100);
+}
+
+#[test]
+fn test_default_ttl_on_create() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_client, token_admin_client) = setup_token(&env, &token_admin);
+
+    let contract_id = env.register(AidEscrow, ());
+    let client = AidEscrowClient::new(&env, &contract_id);
+    client.init(&admin);
+
+    token_admin_client.mint(&admin, &1000);
+    client.fund(&token_client.address, &admin, &1000);
+
+    assert_eq!(
+        client.try_set_default_ttl_extension(&0),
+        Err(Ok(Error::InvalidTtlExtension))
+    );
+    let max_ttl = env.as_contract(&contract_id, || env.storage().max_ttl());
+    assert_eq!(
+        client.try_set_default_ttl_extension(&(max_ttl + 1)),
+        Err(Ok(Error::InvalidTtlExtension))
+    );
+    client.set_default_ttl_extension(&10_000);
+    client.create_package(
+        &admin,
+        &1,
+        &recipient,
+        &100,
+        &token_client.address,
+        &0,
+        &0,
+        &None,
+        &None,
+    );
+    assert_eq!(client.get_package_ttl(&1), 10_000);
+}
+
+#[test]
+fn test_instance_ttl_extension() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let contract_id = env.register(AidEscrow, ());
+    let client = AidEscrowClient::new(&env, &contract_id);
+    client.init(&admin);
+
+    assert_eq!(client.get_instance_ttl(), 0);
+    assert_eq!(
+        client.try_extend_instance_ttl(&0),
+        Err(Ok(Error::InvalidTtlExtension))
+    );
+    assert_eq!(
+        client.try_extend_instance_ttl(&3_110_401),
+        Err(Ok(Error::InvalidTtlExtension))
+    );
+
+    client.extend_instance_ttl(&200_000);
+    assert_eq!(client.get_instance_ttl(), 200_000);
+    let ttl = env.as_contract(&contract_id, || env.storage().instance().get_ttl());
+    assert_eq!(ttl, 200_000);
+    client.extend_instance_ttl(&100_000);
+    assert_eq!(client.get_instance_ttl(), 200_000);
+
+    // Config survives well past the default instance lifetime
+    env.ledger().with_mut(|li| li.sequence_number += 150_000);
+    assert_eq!(client.get_instance_ttl(), 50_000);
+    assert_eq!(client.get_admin(), admin);
+}
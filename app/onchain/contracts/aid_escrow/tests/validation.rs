@@ -0,0 +1,123 @@
+#![cfg(test)]
+
+use aid_escrow::{AidEscrow, AidEscrowClient, Error};
+use soroban_sdk::{
+    Address, Env,
+    testutils::Address as _,
+    token::{StellarAssetClient, TokenClient},
+};
+
+fn setup_token(env: &Env, admin: &Address) -> (TokenClient<'static>, StellarAssetClient<'static>) {
+    let token_contract = env.register_stellar_asset_contract_v2(admin.clone());
+    let token_client = TokenClient::new(env, &token_contract.address());
+    let token_admin_client = StellarAssetClient::new(env, &token_contract.address());
+    (token_client, token_admin_client)
+}
+
+#[test]
+fn test_fund_zero_amount() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_client, token_admin_client) = setup_token(&env, &token_admin);
+
+    let contract_id = env.register(AidEscrow, ());
+    let client = AidEscrowClient::new(&env, &contract_id);
+    client.init(&admin);
+    token_admin_client.mint(&admin, &1000);
+
+    assert_eq!(
+        client.try_fund(&token_client.address, &admin, &0),
+        Err(Ok(Error::InvalidAmount))
+    );
+    assert_eq!(token_client.balance(&contract_id), 0);
+}
+
+#[test]
+fn test_fund_negative_amount() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_client, token_admin_client) = setup_token(&env, &token_admin);
+
+    let contract_id = env.register(AidEscrow, ());
+    let client = AidEscrowClient::new(&env, &contract_id);
+    client.init(&admin);
+    token_admin_client.mint(&admin, &1000);
+
+    assert_eq!(
+        client.try_fund(&token_client.address, &admin, &-1),
+        Err(Ok(Error::InvalidAmount))
+    );
+    assert_eq!(token_client.balance(&admin), 1000);
+}
+
+#[test]
+fn test_create_package_negative_amount() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_client, token_admin_client) = setup_token(&env, &token_admin);
+    let token = token_client.address.clone();
+
+    let contract_id = env.register(AidEscrow, ());
+    let client = AidEscrowClient::new(&env, &contract_id);
+    client.init(&admin);
+    token_admin_client.mint(&admin, &1000);
+    client.fund(&token, &admin, &1000);
+
+    for amount in [0, -1, i128::MIN] {
+        assert_eq!(
+            client.try_create_package(
+                &admin, &1, &recipient, &amount, &token, &0, &0, &None, &None
+            ),
+            Err(Ok(Error::InvalidAmount))
+        );
+    }
+    assert_eq!(client.get_total_locked(&token), 0);
+    assert_eq!(client.get_available_balance(&token), 1000);
+}
+
+#[test]
+fn test_create_package_max_i128_overflow_check() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_client, token_admin_client) = setup_token(&env, &token_admin);
+    let token = token_client.address.clone();
+
+    let contract_id = env.register(AidEscrow, ());
+    let client = AidEscrowClient::new(&env, &contract_id);
+    client.init(&admin);
+    token_admin_client.mint(&admin, &1000);
+    client.fund(&token, &admin, &1000);
+    client.create_package(&admin, &1, &recipient, &10, &token, &0, &0, &None, &None);
+
+    // 10 already locked: `locked + i128::MAX` would overflow, which must read as unbacked
+    assert_eq!(
+        client.try_create_package(
+            &admin,
+            &2,
+            &recipient,
+            &i128::MAX,
+            &token,
+            &0,
+            &0,
+            &None,
+            &None
+        ),
+        Err(Ok(Error::InsufficientFunds))
+    );
+    assert_eq!(client.get_total_locked(&token), 10);
+    assert_eq!(client.get_available_balance(&token), 990);
+}